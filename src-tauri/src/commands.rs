@@ -5,3 +5,22 @@ pub mod game_scanner;
 pub mod game_config;
 pub mod game_launcher;
 pub mod mod_manager;
+pub mod mod_library;
+pub mod mod_user_vars;
+pub mod mod_dependencies;
+pub mod mod_linter;
+pub mod mod_textures;
+pub mod mod_buffers;
+pub mod mod_footprint;
+pub mod mod_load_order;
+pub mod mod_transfer;
+pub mod mod_importer;
+pub mod mod_updates;
+pub mod downloads;
+pub mod deep_link;
+pub mod game_sessions;
+pub mod migoto_logs;
+pub mod game_diagnostics;
+pub mod mod_profiles;
+pub mod launch_hooks;
+pub mod headless;
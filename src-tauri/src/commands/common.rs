@@ -47,17 +47,5 @@ pub fn ensure_directory(path: String) -> Result<(), String> {
 
 #[tauri::command]
 pub fn open_in_explorer(path: String) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    {
-        std::process::Command::new("explorer")
-            .arg(path)
-            .spawn()
-            .map_err(|e| format!("Failed to open explorer: {}", e))?;
-    }
-    #[cfg(not(target_os = "windows"))]
-    {
-        // Simple fallback for linux/mac if needed later
-        // or explicitly fail
-    }
-    Ok(())
+    crate::utils::platform::reveal_in_file_manager(&PathBuf::from(path))
 }
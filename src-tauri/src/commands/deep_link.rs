@@ -0,0 +1,333 @@
+use crate::utils::downloader::{download_file, download_id, get_download_dir};
+use crate::utils::file_manager::get_global_games_dir;
+use crate::utils::mod_paths::check_file_name;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+// One-click install links from mod sites:
+//   ssmt4://install?url=<archive url>&game=<game config>[&name=..&group=..&sha256=..&source=..]
+// The link arrives as a process argument, either on first launch or forwarded by the
+// single instance plugin. Nothing is downloaded until the user confirms the link in a
+// dialog, then the frontend gets "deep-link-ready" and opens the install dialog for that game.
+
+pub const URL_SCHEME: &str = "ssmt4";
+
+// Downloaded links waiting for the frontend, also covers links that arrive before it listens
+pub struct PendingDeepLinks(pub Mutex<Vec<DeepLinkInstall>>);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkInstall {
+    pub id: String, // Download id, matches "download-progress" events
+    pub game_name: String,
+    pub url: String,
+    pub name: Option<String>,
+    pub group: Option<String>,
+    pub sha256: Option<String>,
+    pub source_url: Option<String>, // Mod page, for set_mod_source after install
+    pub archive_path: Option<String>,
+}
+
+pub(crate) fn parse_deep_link(link: &str) -> Result<DeepLinkInstall, String> {
+    let parsed = reqwest::Url::parse(link.trim()).map_err(|e| format!("Invalid link: {}", e))?;
+    if !parsed.scheme().eq_ignore_ascii_case(URL_SCHEME) {
+        return Err(format!("Not an {} link: {}", URL_SCHEME, link));
+    }
+    let action = parsed.host_str().unwrap_or_default();
+    if !action.eq_ignore_ascii_case("install") {
+        return Err(format!("Unsupported link action: {}", action));
+    }
+
+    let param = |key: &str| {
+        parsed
+            .query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    let url = param("url").ok_or("Link is missing the download url")?;
+    let download = reqwest::Url::parse(&url).map_err(|e| format!("Invalid download url: {}", e))?;
+    // Links come from any web page, only fetch over TLS
+    if download.scheme() != "https" {
+        return Err(format!("Only https download urls are allowed: {}", url));
+    }
+
+    let game_name = param("game").ok_or("Link is missing the game")?;
+    check_file_name(&game_name)?;
+
+    let name = param("name").map(|n| check_file_name(&n).map(|n| n.to_string())).transpose()?;
+    let sha256 = param("sha256").map(|h| h.to_lowercase());
+    if let Some(hash) = &sha256 {
+        if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("Invalid sha256 in link".to_string());
+        }
+    }
+
+    Ok(DeepLinkInstall {
+        id: download_id(&url),
+        game_name,
+        url,
+        name,
+        group: param("group"),
+        sha256,
+        source_url: param("source"),
+        archive_path: None,
+    })
+}
+
+// Picks ssmt4:// links out of a process argument list
+pub fn handle_deep_link_args(app: &AppHandle, args: &[String]) {
+    let prefix = format!("{}:", URL_SCHEME);
+    for arg in args.iter().filter(|a| a.to_lowercase().starts_with(&prefix)) {
+        println!("[DeepLink] Received: {}", arg);
+        match parse_deep_link(arg) {
+            Ok(request) => queue_install(app, request),
+            Err(e) => {
+                println!("[DeepLink] Rejected: {}", e);
+                let _ = app.emit("deep-link-error", &e);
+            }
+        }
+    }
+}
+
+fn queue_install(app: &AppHandle, request: DeepLinkInstall) {
+    let config_path = get_global_games_dir(app).join(&request.game_name).join("Config.json");
+    if !config_path.exists() {
+        let e = format!("Game config not found: {}", request.game_name);
+        println!("[DeepLink] Rejected: {}", e);
+        let _ = app.emit("deep-link-error", &e);
+        return;
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+
+    let message = format!(
+        "Download {} for {}?\n\n{}",
+        request.name.as_deref().unwrap_or("this mod"),
+        request.game_name,
+        request.url
+    );
+    let app_handle = app.clone();
+    app.dialog()
+        .message(message)
+        .title("Install mod from link")
+        .kind(MessageDialogKind::Info)
+        .buttons(MessageDialogButtons::OkCancelCustom("Download".to_string(), "Cancel".to_string()))
+        .show(move |confirmed| {
+            if confirmed {
+                start_download(app_handle, request);
+            } else {
+                println!("[DeepLink] Cancelled: {}", request.url);
+            }
+        });
+}
+
+fn start_download(app_handle: AppHandle, mut request: DeepLinkInstall) {
+    let _ = app_handle.emit("deep-link-install", &request);
+    tauri::async_runtime::spawn(async move {
+        let result = match get_download_dir() {
+            Ok(dir) => {
+                download_file(&app_handle, &request.url, &dir, None, request.sha256.as_deref()).await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(path) => {
+                request.archive_path = Some(path.to_string_lossy().to_string());
+                app_handle.state::<PendingDeepLinks>().0.lock().unwrap().push(request.clone());
+                let _ = app_handle.emit("deep-link-ready", &request);
+            }
+            Err(e) => {
+                let _ = app_handle.emit("deep-link-error", format!("{}: {}", request.url, e));
+            }
+        }
+    });
+}
+
+// Downloaded installs the frontend has not handled yet, clears the queue
+#[tauri::command]
+pub fn take_pending_deep_links(state: State<'_, PendingDeepLinks>) -> Result<Vec<DeepLinkInstall>, String> {
+    Ok(std::mem::take(&mut *state.0.lock().unwrap()))
+}
+
+// Point ssmt4:// at this executable (per user, no admin rights needed). Runs in the
+// background and only touches the registry when the handler points somewhere else.
+#[cfg(windows)]
+pub fn register_url_scheme() {
+    std::thread::spawn(|| {
+        if let Err(e) = register_windows_handler() {
+            println!("[DeepLink] Failed to register url scheme: {}", e);
+        }
+    });
+}
+
+// Data of a REG_SZ value, None when the key or value does not exist
+#[cfg(windows)]
+fn reg_query(path: &str, name: Option<&str>) -> Option<String> {
+    let mut cmd = crate::utils::platform::hidden_command("reg");
+    cmd.arg("query").arg(path);
+    match name {
+        Some(n) => cmd.arg("/v").arg(n),
+        None => cmd.arg("/ve"),
+    };
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // "    (Default)    REG_SZ    data", the value name is localized
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|l| l.split_once("REG_SZ").map(|(_, data)| data.trim().to_string()))
+}
+
+// Executable that external launchers (url handlers, shortcuts) should start.
+// AppImages run from a temporary mount, those have to point at the image itself.
+pub(crate) fn launcher_exe() -> Result<std::path::PathBuf, String> {
+    match std::env::var_os("APPIMAGE").filter(|v| !v.is_empty()) {
+        Some(image) => Ok(std::path::PathBuf::from(image)),
+        None => std::env::current_exe().map_err(|e| format!("Failed to find the app executable: {}", e)),
+    }
+}
+
+#[cfg(windows)]
+fn register_windows_handler() -> Result<(), String> {
+    let exe = launcher_exe()?;
+    let key = format!("HKCU\\Software\\Classes\\{}", URL_SCHEME);
+    let command_key = format!("{}\\shell\\open\\command", key);
+    let command = format!("\"{}\" \"%1\"", exe.to_string_lossy());
+    if reg_query(&command_key, None).as_deref() == Some(command.as_str()) {
+        return Ok(());
+    }
+
+    let entries = [
+        (key.clone(), None, "URL:SSMT4 Protocol".to_string()),
+        (key.clone(), Some("URL Protocol"), String::new()),
+        (command_key, None, command),
+    ];
+    for (path, name, data) in entries {
+        let mut cmd = crate::utils::platform::hidden_command("reg");
+        cmd.arg("add").arg(&path);
+        match name {
+            Some(n) => cmd.arg("/v").arg(n),
+            None => cmd.arg("/ve"),
+        };
+        cmd.arg("/d").arg(&data).arg("/f");
+        let output = cmd.output().map_err(|e| format!("Failed to run reg: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+    }
+    println!("[DeepLink] Registered {}:// for {:?}", URL_SCHEME, exe);
+    Ok(())
+}
+
+// Linux desktops resolve ssmt4:// through a desktop entry with a matching MimeType,
+// written per user and only rewritten when the executable moved
+#[cfg(target_os = "linux")]
+pub fn register_url_scheme() {
+    std::thread::spawn(|| {
+        if let Err(e) = register_linux_handler() {
+            println!("[DeepLink] Failed to register url scheme: {}", e);
+        }
+    });
+}
+
+#[cfg(target_os = "linux")]
+fn url_handler_desktop_entry(exe: &std::path::Path) -> String {
+    format!(
+        "[Desktop Entry]\nType=Application\nName=SSMT4\nExec={} %u\nTerminal=false\nNoDisplay=true\nMimeType=x-scheme-handler/{};\n",
+        crate::commands::headless::desktop_exec_quote(&exe.to_string_lossy()),
+        URL_SCHEME
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn register_linux_handler() -> Result<(), String> {
+    use std::path::PathBuf;
+
+    let exe = launcher_exe()?;
+    let data_home = match std::env::var_os("XDG_DATA_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME").ok_or("HOME is not set")?).join(".local/share"),
+    };
+    let applications = data_home.join("applications");
+    let file_name = format!("{}.desktop", URL_SCHEME);
+    let path = applications.join(&file_name);
+
+    let content = url_handler_desktop_entry(&exe);
+    if std::fs::read_to_string(&path).ok().as_deref() == Some(content.as_str()) {
+        return Ok(());
+    }
+    std::fs::create_dir_all(&applications).map_err(|e| format!("Failed to create {:?}: {}", applications, e))?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+
+    let status = std::process::Command::new("xdg-mime")
+        .args(["default", &file_name, &format!("x-scheme-handler/{}", URL_SCHEME)])
+        .status()
+        .map_err(|e| format!("Failed to run xdg-mime: {}", e))?;
+    if !status.success() {
+        return Err(format!("xdg-mime exited with {}", status));
+    }
+    // Optional, only refreshes the mime cache some desktops read
+    let _ = std::process::Command::new("update-desktop-database").arg(&applications).status();
+    println!("[DeepLink] Registered {}:// in {:?}", URL_SCHEME, path);
+    Ok(())
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+pub fn register_url_scheme() {
+    // macOS reads the scheme from the bundle's Info.plist
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[test]
+    fn parses_install_link() {
+        let link = format!(
+            "ssmt4://install?url=https%3A%2F%2Fexample.com%2Fmod.zip&game=WWMI&name=Cool%20Mod&sha256={}&source=https://example.com/mods/1",
+            HASH.to_uppercase()
+        );
+        let request = parse_deep_link(&link).unwrap();
+        assert_eq!(request.url, "https://example.com/mod.zip");
+        assert_eq!(request.game_name, "WWMI");
+        assert_eq!(request.name.as_deref(), Some("Cool Mod"));
+        assert_eq!(request.sha256.as_deref(), Some(HASH));
+        assert_eq!(request.source_url.as_deref(), Some("https://example.com/mods/1"));
+        assert_eq!(request.id, download_id("https://example.com/mod.zip"));
+    }
+
+    #[test]
+    fn requires_https_download() {
+        assert!(parse_deep_link("ssmt4://install?url=http://example.com/mod.zip&game=WWMI").is_err());
+        assert!(parse_deep_link("ssmt4://install?url=file:///etc/passwd&game=WWMI").is_err());
+    }
+
+    #[test]
+    fn rejects_bad_links() {
+        assert!(parse_deep_link("https://install?url=https://example.com/mod.zip&game=WWMI").is_err());
+        assert!(parse_deep_link("ssmt4://remove?url=https://example.com/mod.zip&game=WWMI").is_err());
+        assert!(parse_deep_link("ssmt4://install?url=https://example.com/mod.zip").is_err());
+        assert!(parse_deep_link("ssmt4://install?url=https://example.com/mod.zip&game=..").is_err());
+        assert!(parse_deep_link("ssmt4://install?url=https://example.com/mod.zip&game=WWMI&name=a/b").is_err());
+        assert!(parse_deep_link("ssmt4://install?url=https://example.com/mod.zip&game=WWMI&sha256=abc").is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn desktop_entry_handles_scheme() {
+        let entry = url_handler_desktop_entry(std::path::Path::new("/opt/My Apps/ssmt4"));
+        assert!(entry.contains("Exec=\"/opt/My Apps/ssmt4\" %u\n"));
+        assert!(entry.contains("MimeType=x-scheme-handler/ssmt4;\n"));
+    }
+}
@@ -0,0 +1,46 @@
+use crate::commands::mod_manager::install_mod_archive;
+use crate::utils::downloader::{download_file, get_download_dir};
+use tauri::AppHandle;
+
+// Progress for all of these is reported through "download-progress" events, keyed by
+// the download id (see utils::downloader::download_id).
+
+#[tauri::command]
+pub async fn download_to_cache(
+    app: AppHandle,
+    url: String,
+    file_name: Option<String>,
+    sha256: Option<String>,
+) -> Result<String, String> {
+    let dir = get_download_dir()?;
+    let path = download_file(&app, &url, &dir, file_name.as_deref(), sha256.as_deref()).await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+// Download an archive and install it like a local one, the archive is removed once installed
+#[tauri::command]
+pub async fn download_and_install_mod(
+    app: AppHandle,
+    game_name: String,
+    url: String,
+    target_name: String,
+    target_group: String,
+    file_name: Option<String>,
+    sha256: Option<String>,
+) -> Result<(), String> {
+    let dir = get_download_dir()?;
+    let archive = download_file(&app, &url, &dir, file_name.as_deref(), sha256.as_deref()).await?;
+
+    install_mod_archive(
+        app,
+        game_name,
+        archive.to_string_lossy().to_string(),
+        target_name,
+        target_group,
+        None,
+    )
+    .await?;
+
+    let _ = std::fs::remove_file(&archive);
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use crate::configs::app_config::AppConfig;
 use crate::{commands::game_scanner::BGType, utils::file_manager::get_global_games_dir};
+use crate::utils::downloader::{download_file, get_download_dir};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::io::{Cursor};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -12,6 +12,9 @@ pub struct BasicSettings {
     pub game_preset: String,
     #[serde(default = "default_bg_type")]
     pub background_type: BGType,
+    // Name of the shared mod library under cache_dir/ModLibrary, empty means use game_preset
+    #[serde(default)]
+    pub mod_library: String,
 }
 
 fn default_bg_type() -> BGType {
@@ -23,6 +26,7 @@ impl Default for BasicSettings {
         Self {
             game_preset: "GIMI".to_string(),
             background_type: BGType::Image,
+            mod_library: String::new(),
         }
     }
 }
@@ -196,7 +200,7 @@ pub fn set_game_background(
 
     // Update Config.json with the new type
     let mut config =
-        load_game_config(app.clone(), game_name.clone()).unwrap_or(GameConfig::default());
+        load_game_config(app.clone(), game_name.clone()).unwrap_or_default();
     config.basic.background_type = bg_type;
     save_game_config(app.clone(), game_name, config)
         .map_err(|e| format!("Failed to update config: {}", e))?;
@@ -255,12 +259,12 @@ pub async fn update_game_background(
         .get("game_info_list")
         .and_then(|v| v.as_array())
         .ok_or("Missing game_info_list")?;
-    let first = list.get(0).ok_or("Empty game info list")?;
+    let first = list.first().ok_or("Empty game info list")?;
     let backgrounds = first
         .get("backgrounds")
         .and_then(|v| v.as_array())
         .ok_or("Missing backgrounds")?;
-    let first_bg = backgrounds.get(0).ok_or("Empty backgrounds list")?;
+    let first_bg = backgrounds.first().ok_or("Empty backgrounds list")?;
 
     let target_url = if bg_type == BGType::Video {
         let v_url = first_bg
@@ -287,14 +291,8 @@ pub async fn update_game_background(
 
     println!("Downloading background from: '[{}]'", target_url);
 
-    // Download
-    let download_resp = reqwest::get(target_url)
-        .await
-        .map_err(|e| format!("Download failed: {}", e))?;
-    let bytes = download_resp
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to get bytes: {}", e))?;
+    // Download into the cache first, the current background stays until it is complete
+    let downloaded = download_file(&app, target_url, &get_download_dir()?, None, None).await?;
 
     // Save
     let config_path = get_game_config_path(&app, &game_name);
@@ -339,11 +337,12 @@ pub async fn update_game_background(
         }
     }
 
-    fs::write(&target_path, bytes).map_err(|e| format!("Failed to write file: {}", e))?;
+    fs::copy(&downloaded, &target_path).map_err(|e| format!("Failed to write file: {}", e))?;
+    let _ = fs::remove_file(&downloaded);
 
     // Update config
     let mut config =
-        load_game_config(app.clone(), game_name.clone()).unwrap_or(GameConfig::default());
+        load_game_config(app.clone(), game_name.clone()).unwrap_or_default();
     config.basic.background_type = bg_type.clone();
     save_game_config(app.clone(), game_name, config)
         .map_err(|e| format!("Failed to save config: {}", e))?;
@@ -450,7 +449,7 @@ pub async fn get_3dmigoto_latest_release(game_preset: String) -> Result<UpdateIn
                 .map(|n| n.ends_with(".zip"))
                 .unwrap_or(false)
         })
-        .or(assets.get(0))
+        .or(assets.first())
         .ok_or("No suitable asset found")?;
 
     let download_url = asset
@@ -508,22 +507,25 @@ pub async fn install_3dmigoto_update(
 
     println!("[Update] Downloading update from: {}", download_url);
 
-    let client = reqwest::Client::new();
-    let download_resp = client
-        .get(&download_url)
-        .send()
-        .await
-        .map_err(|e| format!("Download failed: {}", e))?;
-    let bytes = download_resp
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to get bytes: {}", e))?;
-    println!("[Update] Download complete. Size: {} bytes", bytes.len());
+    let package = download_file(&app, &download_url, &get_download_dir()?, None, None).await?;
+    println!(
+        "[Update] Download complete. Size: {} bytes",
+        fs::metadata(&package).map(|m| m.len()).unwrap_or(0)
+    );
 
-    // Unzip
-    let reader = Cursor::new(bytes);
+    install_3dmigoto_package(&app, &package, &target_dir)?;
+    let _ = fs::remove_file(&package);
+
+    println!("[Update] All steps completed successfully.");
+
+    Ok(())
+}
+
+// Extract a downloaded 3Dmigoto package into target_dir and copy the boot files
+pub(crate) fn install_3dmigoto_package(app: &AppHandle, package: &Path, target_dir: &Path) -> Result<(), String> {
+    let file = fs::File::open(package).map_err(|e| format!("Failed to open package: {}", e))?;
     let mut archive =
-        zip::ZipArchive::new(reader).map_err(|e| format!("Failed to read zip: {}", e))?;
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read zip: {}", e))?;
 
     println!("[Update] Extracting {} files...", archive.len());
 
@@ -552,7 +554,7 @@ pub async fn install_3dmigoto_update(
         } else {
             if let Some(p) = outpath.parent() {
                 if !p.exists() {
-                    fs::create_dir_all(&p).map_err(|e| format!("Failed to create dir: {}", e))?;
+                    fs::create_dir_all(p).map_err(|e| format!("Failed to create dir: {}", e))?;
                 }
             }
             let mut outfile =
@@ -566,9 +568,7 @@ pub async fn install_3dmigoto_update(
 
     // Copy Resources
     println!("[Update] Copying essential resources...");
-    crate::utils::file_manager::copy_boot_files(&app, &target_dir);
-
-    println!("[Update] All steps completed successfully.");
+    crate::utils::file_manager::copy_boot_files(app, target_dir);
 
     Ok(())
 }
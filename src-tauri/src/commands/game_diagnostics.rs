@@ -0,0 +1,240 @@
+use crate::commands::game_launcher::{apply_launch_profile, load_migoto_config, resolve_migoto_path};
+use crate::commands::mod_dependencies::{build_dependency_report, DependencyStatus};
+use crate::commands::mod_linter::{lint_ini_file, LintSeverity};
+use crate::commands::mod_load_order::find_override_conflicts;
+use crate::commands::mod_manager::get_mod_scan_depth;
+use crate::utils::mod_ini::{scan_mod_inis, TruncateReason};
+use crate::utils::platform::LaunchRunner;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+// Pre-launch checklist. Every check reports pass/fail, how bad a failure is and what to do
+// about it, so the frontend can render it as-is.
+
+const BOOT_FILES: [&str; 3] = ["d3d11.dll", "d3dcompiler_47.dll", "Run.exe"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticCheck {
+    pub id: String, // Stable identifier, e.g. "target-exe"
+    pub title: String,
+    pub passed: bool,
+    pub severity: LintSeverity, // Info when passed
+    pub message: String,
+    pub suggested_fix: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameDiagnosticsReport {
+    pub game_name: String,
+    pub install_dir: Option<String>,
+    pub ready: bool, // No failed Error checks
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+#[derive(Default)]
+struct Checklist(Vec<DiagnosticCheck>);
+
+impl Checklist {
+    fn pass(&mut self, id: &str, title: &str, message: String) {
+        self.0.push(DiagnosticCheck {
+            id: id.to_string(),
+            title: title.to_string(),
+            passed: true,
+            severity: LintSeverity::Info,
+            message,
+            suggested_fix: None,
+        });
+    }
+
+    fn fail(&mut self, id: &str, title: &str, severity: LintSeverity, message: String, fix: &str) {
+        self.0.push(DiagnosticCheck {
+            id: id.to_string(),
+            title: title.to_string(),
+            passed: false,
+            severity,
+            message,
+            suggested_fix: Some(fix.to_string()),
+        });
+    }
+}
+
+fn check_exe(list: &mut Checklist, id: &str, title: &str, path: Option<&str>, required: bool) {
+    match path.map(str::trim).filter(|p| !p.is_empty()) {
+        None if required => list.fail(id, title, LintSeverity::Error, "Not configured".to_string(), "Select the game executable in the game settings."),
+        None => {}
+        Some(p) if Path::new(p).is_file() => list.pass(id, title, p.to_string()),
+        Some(p) => list.fail(id, title, LintSeverity::Error, format!("File not found: {}", p), "The game may have moved or updated, select the executable again."),
+    }
+}
+
+fn check_runner(list: &mut Checklist, runner: &LaunchRunner) {
+    let title = "Launch runner";
+    match runner {
+        LaunchRunner::Native => {}
+        LaunchRunner::Wine { prefix_path, .. } => {
+            if Path::new(prefix_path.trim()).is_dir() {
+                list.pass("runner", title, format!("Wine prefix: {}", prefix_path));
+            } else {
+                list.fail("runner", title, LintSeverity::Error, format!("Wine prefix not found: {}", prefix_path), "Create the prefix (e.g. run winecfg once) or fix the prefix path.");
+            }
+        }
+        LaunchRunner::Proton { proton_path, compat_data_path, .. } => {
+            let proton = PathBuf::from(proton_path.trim());
+            let script_exists = proton.is_file() || proton.join("proton").is_file();
+            if !script_exists {
+                list.fail("runner", title, LintSeverity::Error, format!("Proton not found: {}", proton_path), "Point the runner at a Proton install folder from steamapps/common or compatibilitytools.d.");
+            } else if compat_data_path.trim().is_empty() {
+                list.fail("runner", title, LintSeverity::Error, "Proton compatdata path is not set".to_string(), "Use the game's steamapps/compatdata/<appid> folder.");
+            } else {
+                list.pass("runner", title, format!("Proton: {}", proton_path));
+            }
+        }
+    }
+}
+
+fn check_writable(dir: &Path) -> Result<(), String> {
+    let probe = dir.join(".ssmt4_write_test");
+    fs::write(&probe, b"").map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+fn check_install_dir(list: &mut Checklist, install_dir: &Path, scan_depth: usize) {
+    let mods_dir = install_dir.join("Mods");
+
+    // Boot files are copied on every launch, missing ones only matter if the copy fails
+    let missing: Vec<&str> = BOOT_FILES.iter().copied().filter(|f| !install_dir.join(f).is_file()).collect();
+    if missing.is_empty() {
+        list.pass("boot-files", "3DMigoto files", BOOT_FILES.join(", "));
+    } else {
+        list.fail("boot-files", "3DMigoto files", LintSeverity::Warning, format!("Missing: {}", missing.join(", ")), "They are copied from the app resources at launch. If this persists, reinstall 3DMigoto from the game settings.");
+    }
+
+    match check_writable(install_dir) {
+        Ok(()) => list.pass("install-dir-writable", "Install folder writable", install_dir.to_string_lossy().to_string()),
+        Err(e) => list.fail("install-dir-writable", "Install folder writable", LintSeverity::Error, format!("Cannot write to {:?}: {}", install_dir, e), "Move 3DMigoto out of a protected folder (like Program Files) or fix the folder permissions."),
+    }
+
+    let d3dx = install_dir.join("d3dx.ini");
+    if !d3dx.is_file() {
+        list.fail("d3dx-ini", "d3dx.ini", LintSeverity::Error, "d3dx.ini not found".to_string(), "Reinstall the 3DMigoto package for this game.");
+    } else {
+        let errors: Vec<String> = lint_ini_file(&d3dx)
+            .into_iter()
+            .filter(|i| i.severity == LintSeverity::Error)
+            .map(|i| format!("line {}: {}", i.line, i.message))
+            .collect();
+        if errors.is_empty() {
+            list.pass("d3dx-ini", "d3dx.ini", "Parsed without errors".to_string());
+        } else {
+            list.fail("d3dx-ini", "d3dx.ini", LintSeverity::Error, errors.join("\n"), "Fix the listed lines, or reinstall 3DMigoto to restore a clean d3dx.ini.");
+        }
+    }
+
+    if !mods_dir.is_dir() {
+        list.fail("mods-folder", "Mods folder", LintSeverity::Warning, format!("Not found: {:?}", mods_dir), "Create it from the mod manager, or install a mod to create it.");
+        return;
+    }
+    list.pass("mods-folder", "Mods folder", mods_dir.to_string_lossy().to_string());
+
+    let scan = scan_mod_inis(&mods_dir, scan_depth);
+    let too_deep: Vec<&str> = scan
+        .truncated
+        .iter()
+        .filter(|t| t.reason == TruncateReason::DepthLimit)
+        .map(|t| t.relative_path.as_str())
+        .collect();
+    if !too_deep.is_empty() {
+        list.fail("mod-scan-depth", "Mod scan depth", LintSeverity::Warning,
+            format!("Not checked, deeper than {} folder level(s): {}", scan_depth, too_deep.join(", ")),
+            "Raise the mod scan depth in the game settings, or move these mods up.");
+    }
+
+    let conflicts = find_override_conflicts(&mods_dir, scan_depth);
+    if conflicts.is_empty() {
+        list.pass("mod-conflicts", "Conflicting mods", "No enabled mods override the same hash".to_string());
+    } else {
+        let mods: BTreeSet<&str> = conflicts.iter().flat_map(|c| c.claims.iter().map(|cl| cl.mod_relative_path.as_str())).collect();
+        list.fail("mod-conflicts", "Conflicting mods", LintSeverity::Warning,
+            format!("{} hash(es) overridden by more than one mod: {}", conflicts.len(), mods.into_iter().collect::<Vec<_>>().join(", ")),
+            "Disable one of the mods, or set priorities so the intended one loads last.");
+    }
+
+    let mut lint_errors: BTreeMap<String, usize> = BTreeMap::new();
+    for file in scan.files.into_iter().filter(|f| f.enabled) {
+        let errors = lint_ini_file(&file.ini_path).iter().filter(|i| i.severity == LintSeverity::Error).count();
+        if errors > 0 {
+            *lint_errors.entry(file.mod_relative_path).or_default() += errors;
+        }
+    }
+    if lint_errors.is_empty() {
+        list.pass("mod-lint", "Mod ini errors", "No errors in enabled mods".to_string());
+    } else {
+        let summary: Vec<String> = lint_errors.iter().map(|(m, n)| format!("{} ({})", m, n)).collect();
+        list.fail("mod-lint", "Mod ini errors", LintSeverity::Error, summary.join(", "), "Open the mod's lint report for the exact lines, or disable the mod.");
+    }
+
+    let dependencies = build_dependency_report(install_dir, scan_depth);
+    if dependencies.unresolved.is_empty() {
+        list.pass("mod-dependencies", "Mod dependencies", "All referenced namespaces are available".to_string());
+    } else {
+        let summary: Vec<String> = dependencies
+            .unresolved
+            .iter()
+            .map(|u| match u.status {
+                DependencyStatus::Missing => format!("{} needs {} (not installed)", u.mod_relative_path, u.namespace),
+                DependencyStatus::Disabled => format!("{} needs {} (disabled)", u.mod_relative_path, u.namespace),
+            })
+            .collect();
+        list.fail("mod-dependencies", "Mod dependencies", LintSeverity::Error, summary.join("\n"), "Install or enable the required mods, or disable the mods that need them.");
+    }
+    if !dependencies.duplicates.is_empty() {
+        let names: Vec<&str> = dependencies.duplicates.iter().map(|d| d.namespace.as_str()).collect();
+        list.fail("duplicate-namespaces", "Duplicate namespaces", LintSeverity::Warning, format!("Declared more than once: {}", names.join(", ")), "Keep only one copy of each mod enabled.");
+    }
+}
+
+// profile: check the settings start_game would use with that launch profile (default profile when None)
+#[tauri::command]
+pub fn diagnose_game(app: AppHandle, game_name: String, profile: Option<String>) -> Result<GameDiagnosticsReport, String> {
+    let mut list = Checklist::default();
+    let mut install_dir = None;
+
+    match load_migoto_config(&app, &game_name) {
+        Err(e) => list.fail("config", "Game config", LintSeverity::Error, e, "Re-create the game config from the game list."),
+        Ok(mut config) => {
+            list.pass("config", "Game config", "Loaded".to_string());
+            if let Err(e) = apply_launch_profile(&mut config, profile.as_deref()) {
+                list.fail("launch-profile", "Launch profile", LintSeverity::Error, e, "Pick an existing launch profile, or fix the default launch profile in the game settings.");
+            }
+            check_exe(&mut list, "target-exe", "Game executable", config.target_exe_path.as_deref(), true);
+            // Started by us in shell mode, by Run.exe ([Loader] launch) otherwise
+            check_exe(&mut list, "launcher-exe", "Launcher executable", config.launcher_exe_path.as_deref(), false);
+            check_runner(&mut list, &config.runner.clone().unwrap_or_default());
+
+            match resolve_migoto_path(&app, &game_name, &config) {
+                Err(e) => list.fail("install-dir", "3DMigoto folder", LintSeverity::Error, e, "Set the 3DMigoto folder in the game settings, or set a cache folder in the app settings."),
+                Ok(dir) if !dir.is_dir() => list.fail("install-dir", "3DMigoto folder", LintSeverity::Error, format!("Not found: {:?}", dir), "Install 3DMigoto from the game settings."),
+                Ok(dir) => {
+                    list.pass("install-dir", "3DMigoto folder", dir.to_string_lossy().to_string());
+                    check_install_dir(&mut list, &dir, get_mod_scan_depth(&app, &game_name));
+                    install_dir = Some(dir.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+
+    let ready = !list.0.iter().any(|c| !c.passed && c.severity == LintSeverity::Error);
+    println!("[Diagnostics] {}: {} checks, ready: {}", game_name, list.0.len(), ready);
+    Ok(GameDiagnosticsReport {
+        game_name,
+        install_dir,
+        ready,
+        checks: list.0,
+    })
+}
@@ -1,6 +1,11 @@
+use crate::commands::game_sessions::track_game_session;
+use crate::commands::launch_hooks::{run_hooks, HookStage, LaunchHooks};
+use crate::commands::migoto_logs::tail_3dmigoto_logs;
+use crate::commands::mod_profiles::apply_mod_profile;
 use crate::utils::file_manager::get_global_games_dir;
 use crate::utils::ini_manager::IniManager;
-use serde::Deserialize;
+use crate::utils::platform::{split_launch_args, start_process_with, to_runner_path, LaunchRunner, LaunchSpec};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::process::Command;
 use tauri::AppHandle;
@@ -14,56 +19,51 @@ struct GameConfigFull {
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
-struct ThreeDMigotoConfig {
-    install_dir: Option<String>,
-    target_exe_path: Option<String>,
-    launcher_exe_path: Option<String>,
-    launch_args: Option<String>,
-    use_shell: Option<bool>,
-    show_error_popup: Option<bool>,
-    auto_set_analyse_options: Option<bool>,
-    delay: Option<i32>,
-    extra_dll: Option<String>,
-    auto_exit_seconds: Option<i32>,
-    use_upx: Option<bool>,
+pub(crate) struct ThreeDMigotoConfig {
+    pub install_dir: Option<String>,
+    pub target_exe_path: Option<String>,
+    pub launcher_exe_path: Option<String>,
+    pub launch_args: Option<String>,
+    pub use_shell: Option<bool>,
+    pub show_error_popup: Option<bool>,
+    pub auto_set_analyse_options: Option<bool>,
+    pub delay: Option<i32>,
+    pub extra_dll: Option<String>,
+    pub auto_exit_seconds: Option<i32>,
+    pub use_upx: Option<bool>,
+    pub runner: Option<LaunchRunner>,
+    pub launch_profiles: Option<Vec<LaunchProfile>>,
+    pub default_launch_profile: Option<String>,
+    pub hooks: Option<LaunchHooks>,
 }
 
-fn resolve_migoto_path(app: &AppHandle, game_name: &str, config: &ThreeDMigotoConfig) -> Result<PathBuf, String> {
-    //告诉编译器，我知道app在这儿，但是暂时用不上，未来可能重构使用到它，所以保留，防止出现黄线
-    let _ = app;
-    
-    if let Some(ref p) = config.install_dir {
-        if !p.trim().is_empty() {
-             return Ok(PathBuf::from(p));
-        }
-    }
-    
-    use crate::configs::app_config::AppConfig;
-    let app_config = AppConfig::load().map_err(|e| format!("Failed to load app config: {}", e))?;
-    let cache_dir = PathBuf::from(&app_config.cache_dir);
-    if app_config.cache_dir.is_empty() {
-        return Err("3Dmigoto Path not set and Cache Dir not set.".into());
-    }
-    Ok(cache_dir.join("3Dmigoto").join(game_name))
+// Named override of the launch settings, unset fields fall back to the main config
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchProfile {
+    pub name: String,
+    pub target_exe_path: Option<String>,
+    pub launcher_exe_path: Option<String>,
+    pub launch_args: Option<String>,
+    pub use_shell: Option<bool>,
+    pub delay: Option<i32>,
+    pub extra_dll: Option<String>,
+    pub mod_profile: Option<String>, // Mod profile applied before launching
 }
 
-#[tauri::command]
-pub async fn check_3dmigoto_integrity(app: AppHandle, game_name: String) -> Result<bool, String> {
-    let games_dir = get_global_games_dir(&app);
-    let game_dir = games_dir.join(&game_name);
-    let config_path = game_dir.join("Config.json");
-
+// threeDMigoto section of the game's Config.json, defaults when the section is missing
+pub(crate) fn load_migoto_config(app: &AppHandle, game_name: &str) -> Result<ThreeDMigotoConfig, String> {
+    let config_path = get_global_games_dir(app).join(game_name).join("Config.json");
     if !config_path.exists() {
         return Err(format!("Config file not found: {:?}", config_path));
     }
 
     let config_content = std::fs::read_to_string(&config_path)
         .map_err(|e| format!("Failed to read config: {}", e))?;
-    
     let config_full: GameConfigFull = serde_json::from_str(&config_content)
         .map_err(|e| format!("Failed to parse config: {}", e))?;
 
-    let migoto_config = config_full.three_d_migoto.unwrap_or(ThreeDMigotoConfig {
+    Ok(config_full.three_d_migoto.unwrap_or(ThreeDMigotoConfig {
         install_dir: None,
         target_exe_path: None,
         launcher_exe_path: None,
@@ -75,48 +75,95 @@ pub async fn check_3dmigoto_integrity(app: AppHandle, game_name: String) -> Resu
         extra_dll: None,
         auto_exit_seconds: None,
         use_upx: None,
-    });
+        runner: None,
+        launch_profiles: None,
+        default_launch_profile: None,
+        hooks: None,
+    }))
+}
 
-    let migoto_path = resolve_migoto_path(&app, &game_name, &migoto_config)?;
-    
-    let d3d11 = migoto_path.join("d3d11.dll");
-    let d3dx = migoto_path.join("d3dx.ini");
+// Overlay the chosen (or default) launch profile, returns its mod profile if it has one
+pub(crate) fn apply_launch_profile(config: &mut ThreeDMigotoConfig, profile_name: Option<&str>) -> Result<Option<String>, String> {
+    let name = match profile_name.or(config.default_launch_profile.as_deref()).map(str::trim).filter(|n| !n.is_empty()) {
+        Some(n) => n.to_string(),
+        None => return Ok(None),
+    };
+    let profile = config
+        .launch_profiles
+        .iter()
+        .flatten()
+        .find(|p| p.name.eq_ignore_ascii_case(&name))
+        .cloned()
+        .ok_or_else(|| format!("Launch profile not found: {}", name))?;
+    println!("[GameLauncher] Using launch profile: {}", profile.name);
+
+    if profile.target_exe_path.is_some() {
+        config.target_exe_path = profile.target_exe_path;
+    }
+    if profile.launcher_exe_path.is_some() {
+        config.launcher_exe_path = profile.launcher_exe_path;
+    }
+    if profile.launch_args.is_some() {
+        config.launch_args = profile.launch_args;
+    }
+    if profile.use_shell.is_some() {
+        config.use_shell = profile.use_shell;
+    }
+    if profile.delay.is_some() {
+        config.delay = profile.delay;
+    }
+    if profile.extra_dll.is_some() {
+        config.extra_dll = profile.extra_dll;
+    }
+    Ok(profile.mod_profile.filter(|m| !m.trim().is_empty()))
+}
 
-    Ok(d3d11.exists() && d3dx.exists())
+// Launcher if set, else the game. A profile clears the launcher with "" to start the exe directly.
+fn shell_launch_exe(config: &ThreeDMigotoConfig) -> Option<String> {
+    let non_empty = |p: &Option<String>| p.as_deref().map(str::trim).filter(|p| !p.is_empty()).map(|p| p.to_string());
+    non_empty(&config.launcher_exe_path).or_else(|| non_empty(&config.target_exe_path))
 }
 
 #[tauri::command]
-pub async fn start_game(app: AppHandle, game_name: String) -> Result<(), String> {
-    let games_dir = get_global_games_dir(&app);
-    let game_dir = games_dir.join(&game_name);
-    let config_path = game_dir.join("Config.json");
+pub fn list_launch_profiles(app: AppHandle, game_name: String) -> Result<Vec<LaunchProfile>, String> {
+    Ok(load_migoto_config(&app, &game_name)?.launch_profiles.unwrap_or_default())
+}
 
-    if !config_path.exists() {
-        return Err(format!("Config file not found: {:?}", config_path));
+pub(crate) fn resolve_migoto_path(app: &AppHandle, game_name: &str, config: &ThreeDMigotoConfig) -> Result<PathBuf, String> {
+    //告诉编译器，我知道app在这儿，但是暂时用不上，未来可能重构使用到它，所以保留，防止出现黄线
+    let _ = app;
+    
+    if let Some(ref p) = config.install_dir {
+        if !p.trim().is_empty() {
+             return Ok(PathBuf::from(p));
+        }
     }
+    
+    use crate::configs::app_config::AppConfig;
+    let app_config = AppConfig::load().map_err(|e| format!("Failed to load app config: {}", e))?;
+    let cache_dir = PathBuf::from(&app_config.cache_dir);
+    if app_config.cache_dir.is_empty() {
+        return Err("3Dmigoto Path not set and Cache Dir not set.".into());
+    }
+    Ok(cache_dir.join("3Dmigoto").join(game_name))
+}
 
-    let config_content = std::fs::read_to_string(&config_path)
-        .map_err(|e| format!("Failed to read config: {}", e))?;
+#[tauri::command]
+pub async fn check_3dmigoto_integrity(app: AppHandle, game_name: String) -> Result<bool, String> {
+    let migoto_config = load_migoto_config(&app, &game_name)?;
+
+    let migoto_path = resolve_migoto_path(&app, &game_name, &migoto_config)?;
     
-    // Log the config content to verify structure if needed (optional)
-    // println!("[GameLauncher] Config Content: {}", config_content);
+    let d3d11 = migoto_path.join("d3d11.dll");
+    let d3dx = migoto_path.join("d3dx.ini");
 
-    let config_full: GameConfigFull = serde_json::from_str(&config_content)
-        .map_err(|e| format!("Failed to parse config: {}", e))?;
+    Ok(d3d11.exists() && d3dx.exists())
+}
 
-    let migoto_config = config_full.three_d_migoto.unwrap_or(ThreeDMigotoConfig {
-        install_dir: None,
-        target_exe_path: None,
-        launcher_exe_path: None,
-        launch_args: None,
-        use_shell: Some(false),
-        show_error_popup: Some(false),
-        auto_set_analyse_options: Some(false),
-        delay: None,
-        extra_dll: None,
-        auto_exit_seconds: None,
-        use_upx: None,
-    });
+#[tauri::command]
+pub async fn start_game(app: AppHandle, game_name: String, profile: Option<String>) -> Result<(), String> {
+    let mut migoto_config = load_migoto_config(&app, &game_name)?;
+    let mod_profile = apply_launch_profile(&mut migoto_config, profile.as_deref())?;
 
     let migoto_path = resolve_migoto_path(&app, &game_name, &migoto_config)?;
     
@@ -132,6 +179,19 @@ pub async fn start_game(app: AppHandle, game_name: String) -> Result<(), String>
         return Err(format!("配置的游戏进程文件不存在: {}\n请检查路径是否正确。", target_exe));
     }
 
+    // Pre-launch hooks run before anything touches the 3DMigoto folder
+    let hooks = migoto_config.hooks.clone().unwrap_or_default();
+    let hook_env = vec![
+        ("SSMT4_GAME_EXE", target_path.to_string_lossy().to_string()),
+        ("SSMT4_MIGOTO_DIR", migoto_path.to_string_lossy().to_string()),
+    ];
+    run_hooks(&app, &game_name, HookStage::PreLaunch, &hooks.pre_launch, hook_env).await?;
+
+    // Switch the enabled mods to the launch profile's mod profile
+    if let Some(mod_profile) = mod_profile {
+        apply_mod_profile(app.clone(), game_name.clone(), mod_profile).await?;
+    }
+
     // Copy essential boot files
     crate::utils::file_manager::copy_boot_files(&app, &migoto_path);
     
@@ -197,11 +257,13 @@ pub async fn start_game(app: AppHandle, game_name: String) -> Result<(), String>
 
     // Load INI
     let mut ini = IniManager::load(&d3dx_path)?;
+    // Run.exe inside Wine / Proton only understands Windows paths
+    let runner = migoto_config.runner.clone().unwrap_or_default();
 
     // 1. [Loader] target
     if let Some(target) = &migoto_config.target_exe_path {
         if !target.is_empty() {
-             ini.set("Loader", "target", target);
+             ini.set("Loader", "target", &to_runner_path(&runner, target));
         }
     }
     
@@ -216,7 +278,7 @@ pub async fn start_game(app: AppHandle, game_name: String) -> Result<(), String>
         // If run_shell is FALSE, set them to valid paths
         if let Some(launch) = &migoto_config.launcher_exe_path {
             if !launch.is_empty() {
-                ini.set("Loader", "launch", launch);
+                ini.set("Loader", "launch", &to_runner_path(&runner, launch));
             } else {
                 ini.remove_key("Loader", "launch");
             }
@@ -265,7 +327,7 @@ pub async fn start_game(app: AppHandle, game_name: String) -> Result<(), String>
     // 9. [Loader] inject_dll
     if let Some(dll) = &migoto_config.extra_dll {
         if !dll.is_empty() {
-            ini.set("Loader", "inject_dll", dll);
+            ini.set("Loader", "inject_dll", &to_runner_path(&runner, dll));
         } else {
              ini.remove_key("Loader", "inject_dll");
         }
@@ -284,17 +346,10 @@ pub async fn start_game(app: AppHandle, game_name: String) -> Result<(), String>
         return Err(format!("{} not found in 3DMigoto directory: {:?}", run_exe_name, migoto_path));
     }
     
-    // Launch Run.exe using PowerShell (fixes UAC focus issues)
-    let run_script = format!(
-        "Start-Process -FilePath '{}' -WorkingDirectory '{}'", 
-        run_exe_name, 
-        migoto_path.to_string_lossy()
-    );
-    
-    Command::new("powershell")
-       .args(&["-NoProfile", "-Command", &run_script])
-       .spawn()
-       .map_err(|e| format!("Failed to launch {}: {}", run_exe_name, e))?;
+    // Launch Run.exe (through PowerShell on Windows, fixes UAC focus issues)
+    start_process_with(&runner, &LaunchSpec::new(&run_exe, &migoto_path))
+        .map_err(|e| format!("Failed to launch {}: {}", run_exe_name, e))?;
+    let mut game_child = None;
 
     // 11. If Shell Mode is enabled, we manually launch the game target
     // Run.exe won't do it because we removed the [Loader] launch key in INI.
@@ -319,9 +374,14 @@ pub async fn start_game(app: AppHandle, game_name: String) -> Result<(), String>
              let refresh_kind = ProcessRefreshKind::new().with_exe(UpdateKind::Always);
              sys.refresh_processes_specifics(refresh_kind);
 
-             for (_pid, process) in sys.processes() {
+             for process in sys.processes().values() {
                  let proc_name = std::path::Path::new(process.name()).to_string_lossy().to_lowercase();
                  if proc_name == "run.exe" {
+                     // Under Wine / Proton exe() is the wine loader, the name is all we have
+                     if runner != LaunchRunner::Native {
+                         found = true;
+                         break;
+                     }
                      if let Some(exe_path) = process.exe() {
                          if exe_path.to_string_lossy().to_lowercase() == run_exe_path_str {
                              found = true;
@@ -352,37 +412,29 @@ pub async fn start_game(app: AppHandle, game_name: String) -> Result<(), String>
         }
 
         // Run Target Executable via Shell
-        let exe_path = migoto_config.launcher_exe_path
-             .or(migoto_config.target_exe_path)
-             .ok_or("No executable path found for shell launch")?;
-
-        if exe_path.is_empty() {
-             return Err("Launch path is empty".into());
-        }
+        let exe_path = shell_launch_exe(&migoto_config).ok_or("No executable path found for shell launch")?;
         let args = migoto_config.launch_args.unwrap_or_default();
         
         let path_obj = PathBuf::from(&exe_path);
         let default_work_dir = PathBuf::from(".");
         let work_dir = path_obj.parent().unwrap_or(&default_work_dir);
 
-        // Use Powershell Start-Process
-        let mut ps_script = format!(
-            "Start-Process -FilePath '{}' -WorkingDirectory '{}'", 
-            exe_path, 
-            work_dir.to_string_lossy()
-        );
-        
-        if !args.is_empty() {
-             ps_script.push_str(&format!(" -ArgumentList '{}'", args));
-        }
-        
         println!("[GameLauncher] Launching target via shell: {}", exe_path);
-        Command::new("powershell")
-           .args(&["-NoProfile", "-Command", &ps_script])
-           .spawn()
+        let spec = LaunchSpec::new(&path_obj, work_dir).args(split_launch_args(&args));
+        let child = start_process_with(&runner, &spec)
            .map_err(|e| format!("Failed to launch shell command: {}", e))?;
+        // Only the game's own process tells us the exit code, not a separate launcher's
+        if path_obj == target_path {
+            game_child = child;
+        }
     }
 
+    // 12. Watch the game process for playtime tracking
+    track_game_session(&app, &game_name, &target_path, runner != LaunchRunner::Native, game_child, hooks);
+
+    // 13. Surface 3DMigoto warnings from its log while the game loads
+    tail_3dmigoto_logs(&app, &game_name, &migoto_path);
+
     Ok(())
 }
 
@@ -448,3 +500,55 @@ pub fn toggle_symlink(app: AppHandle, game_name: String, enable: bool) -> Result
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ThreeDMigotoConfig {
+        serde_json::from_str(
+            r#"{
+                "targetExePath": "C:/Game/Game.exe",
+                "launcherExePath": "C:/Game/Launcher.exe",
+                "launchArgs": "-a",
+                "useShell": true,
+                "defaultLaunchProfile": "Launcher",
+                "launchProfiles": [
+                    { "name": "Direct", "launcherExePath": "", "launchArgs": "-popupwindow" },
+                    { "name": "Launcher", "delay": 5, "modProfile": "Clean" }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn profile_can_switch_to_a_direct_launch() {
+        let mut c = config();
+        assert_eq!(apply_launch_profile(&mut c, Some("direct")).unwrap(), None);
+        assert_eq!(shell_launch_exe(&c).as_deref(), Some("C:/Game/Game.exe"));
+        assert_eq!(c.launch_args.as_deref(), Some("-popupwindow"));
+    }
+
+    #[test]
+    fn default_profile_applies_and_keeps_unset_fields() {
+        let mut c = config();
+        assert_eq!(apply_launch_profile(&mut c, None).unwrap().as_deref(), Some("Clean"));
+        assert_eq!(c.delay, Some(5));
+        assert_eq!(shell_launch_exe(&c).as_deref(), Some("C:/Game/Launcher.exe"));
+        assert_eq!(c.launch_args.as_deref(), Some("-a"));
+    }
+
+    #[test]
+    fn unknown_profile_is_an_error() {
+        assert!(apply_launch_profile(&mut config(), Some("Nope")).is_err());
+    }
+
+    #[test]
+    fn blank_paths_are_not_launchable() {
+        let mut c = config();
+        c.launcher_exe_path = Some("  ".to_string());
+        c.target_exe_path = Some(String::new());
+        assert_eq!(shell_launch_exe(&c), None);
+    }
+}
@@ -1,4 +1,5 @@
-﻿use crate::utils::file_manager::get_global_games_dir;
+﻿use crate::commands::game_sessions::load_play_stats;
+use crate::utils::file_manager::get_global_games_dir;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
@@ -22,6 +23,8 @@ pub struct GameInfo {
     pub bg_video_path: Option<PathBuf>,
     pub bg_type: BGType, // "image" or "video"
     pub show_sidebar: bool,
+    pub last_played: Option<u64>, // Unix seconds, from PlayStats.json
+    pub total_play_seconds: u64,
 }
 
 #[derive(Deserialize)]
@@ -59,6 +62,7 @@ fn find_games_dir(app: &AppHandle) -> PathBuf {
 
 // Normalize paths: strip Windows extended prefix and force forward slashes so convertFileSrc gets a POSIX-ish path
 fn normalize_path(p: &Path) -> String {
+    #[allow(unused_mut)]
     let mut s = p.to_string_lossy().to_string();
     #[cfg(windows)]
     {
@@ -94,65 +98,67 @@ pub fn scan_games(app: AppHandle) -> Result<Vec<GameInfo>, String> {
     let entries =
         fs::read_dir(&games_dir).map_err(|e| format!("Failed to read games directory: {}", e))?;
 
-    for entry in entries {
-        if let Ok(entry) = entry {
-            let path = entry.path();
-            if path.is_dir() {
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    // 构建图片路径
-                    let mut bg_path = path.join("Background.png");
-                    if !bg_path.exists() {
-                        bg_path = path.join("Background.webp");
-                    }
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                // 构建图片路径
+                let mut bg_path = path.join("Background.png");
+                if !bg_path.exists() {
+                    bg_path = path.join("Background.webp");
+                }
 
-                    let icon_path = path.join("Icon.png");
+                let icon_path = path.join("Icon.png");
 
-                    // Check for video
-                    let mut video_path = path.join("Background.mp4");
-                    if !video_path.exists() {
-                        video_path = path.join("Background.webm");
-                    }
-                    let video_path = if video_path.exists() {
-                        Some(video_path)
-                    } else {
-                        None
-                    };
-
-                    // Determine background type from Config.json
-                    let mut bg_type = BGType::Image;
-                    let config_path = path.join("Config.json");
-                    if config_path.exists() {
-                        if let Ok(content) = fs::read_to_string(&config_path) {
-                            if let Ok(config) = serde_json::from_str::<PartialGameConfig>(&content)
-                            {
-                                if let Some(t) = config.basic.background_type {
-                                    bg_type = t;
-                                }
+                // Check for video
+                let mut video_path = path.join("Background.mp4");
+                if !video_path.exists() {
+                    video_path = path.join("Background.webm");
+                }
+                let video_path = if video_path.exists() {
+                    Some(video_path)
+                } else {
+                    None
+                };
+
+                // Determine background type from Config.json
+                let mut bg_type = BGType::Image;
+                let config_path = path.join("Config.json");
+                if config_path.exists() {
+                    if let Ok(content) = fs::read_to_string(&config_path) {
+                        if let Ok(config) = serde_json::from_str::<PartialGameConfig>(&content)
+                        {
+                            if let Some(t) = config.basic.background_type {
+                                bg_type = t;
                             }
                         }
                     }
+                }
 
-                    // let icon_str = normalize_path(&icon_path);
-                    // let bg_str = normalize_path(&bg_path);
+                // let icon_str = normalize_path(&icon_path);
+                // let bg_str = normalize_path(&bg_path);
 
-                    // 不论文件是否存在都加入列表
-                    if !icon_path.exists() {
-                        println!("Warning: Icon missing for {}: {:?}", name, icon_path);
-                    }
-                    // Background warnings might be spammy if only video exists, but keeps consistent
-
-                    // Determine show_sidebar status
-                    let show_sidebar = *sidebar_config.get(name).unwrap_or(&false);
-
-                    games.push(GameInfo {
-                        name: name.to_string(),
-                        icon_path,
-                        bg_path,
-                        bg_video_path: video_path,
-                        bg_type,
-                        show_sidebar,
-                    });
+                // 不论文件是否存在都加入列表
+                if !icon_path.exists() {
+                    println!("Warning: Icon missing for {}: {:?}", name, icon_path);
                 }
+                // Background warnings might be spammy if only video exists, but keeps consistent
+
+                // Determine show_sidebar status
+                let show_sidebar = *sidebar_config.get(name).unwrap_or(&false);
+
+                let play_stats = load_play_stats(&path);
+
+                games.push(GameInfo {
+                    name: name.to_string(),
+                    icon_path,
+                    bg_path,
+                    bg_video_path: video_path,
+                    bg_type,
+                    show_sidebar,
+                    last_played: play_stats.last_played,
+                    total_play_seconds: play_stats.total_seconds,
+                });
             }
         }
     }
@@ -0,0 +1,240 @@
+use crate::commands::launch_hooks::{run_hooks, HookStage, LaunchHooks};
+use crate::utils::file_manager::get_global_games_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sysinfo::{Pid, ProcessRefreshKind, System, UpdateKind};
+use tauri::{AppHandle, Emitter, Manager};
+
+// Playtime tracking. After start_game the target exe is watched until it exits and the
+// session is appended to Games/<game>/PlayStats.json.
+
+const PLAY_STATS_FILE: &str = "PlayStats.json";
+const MAX_SESSION_HISTORY: usize = 200;
+// Run.exe and launchers can take a while before the game itself shows up
+const START_TIMEOUT: Duration = Duration::from_secs(300);
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// One watcher per game, a new launch replaces the previous one
+pub struct GameSessionTracker(pub Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameSession {
+    pub started_at: u64, // Unix seconds
+    pub ended_at: u64,
+    pub duration_seconds: u64,
+    pub exit_code: Option<i32>, // Only known when the launcher spawned the game itself
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayStats {
+    #[serde(default)]
+    pub total_seconds: u64,
+    #[serde(default)]
+    pub last_played: Option<u64>,
+    #[serde(default)]
+    pub sessions: Vec<GameSession>, // Oldest first
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct GameStartedEvent {
+    game_name: String,
+    pid: u32,
+    started_at: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct GameExitedEvent {
+    game_name: String,
+    exit_code: Option<i32>,
+    session: GameSession,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+pub fn load_play_stats(game_dir: &Path) -> PlayStats {
+    fs::read_to_string(game_dir.join(PLAY_STATS_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn record_session(game_dir: &Path, session: GameSession) -> Result<(), String> {
+    let mut stats = load_play_stats(game_dir);
+    stats.total_seconds += session.duration_seconds;
+    stats.last_played = Some(session.ended_at);
+    stats.sessions.push(session);
+    if stats.sessions.len() > MAX_SESSION_HISTORY {
+        let excess = stats.sessions.len() - MAX_SESSION_HISTORY;
+        stats.sessions.drain(..excess);
+    }
+    let content = serde_json::to_string_pretty(&stats).map_err(|e| format!("Failed to serialize play stats: {}", e))?;
+    fs::write(game_dir.join(PLAY_STATS_FILE), content).map_err(|e| format!("Failed to write play stats: {}", e))
+}
+
+fn file_name_lower(path: &str) -> String {
+    path.rsplit(['/', '\\']).next().unwrap_or(path).to_lowercase()
+}
+
+// Whether a process is the game: same exe path, or the same file name under Wine where exe()
+// is the wine loader. Elevated games (UAC) hide exe() from an unelevated launcher, the name
+// is all there is then, same as the Run.exe detection.
+// Linux also cuts process names to 15 characters, so the command line is checked too.
+fn is_game_process(exe: Option<&Path>, name: &str, first_arg: Option<&str>, target_path: &str, by_name: bool) -> bool {
+    let target_name = file_name_lower(target_path);
+    let name_matches = || name.to_lowercase() == target_name || first_arg.map(|c| file_name_lower(c) == target_name).unwrap_or(false);
+    match exe {
+        Some(p) if p.to_string_lossy().to_lowercase() == target_path => true,
+        Some(_) => by_name && name_matches(),
+        None => name_matches(),
+    }
+}
+
+fn find_game_pid(sys: &mut System, target_exe: &Path, by_name: bool) -> Option<Pid> {
+    sys.refresh_processes_specifics(ProcessRefreshKind::new().with_exe(UpdateKind::Always).with_cmd(UpdateKind::Always));
+
+    let target_path = target_exe.to_string_lossy().to_lowercase();
+    sys.processes().iter().find_map(|(pid, process)| {
+        let first_arg = process.cmd().first().map(|c| c.as_str());
+        is_game_process(process.exe(), process.name(), first_arg, &target_path, by_name).then_some(*pid)
+    })
+}
+
+async fn watch_session(app: AppHandle, game_name: String, target_exe: PathBuf, by_name: bool, mut child: Option<Child>, hooks: LaunchHooks) {
+    let mut sys = System::new();
+
+    let pid = match &child {
+        Some(c) => Pid::from_u32(c.id()),
+        None => {
+            let start = std::time::Instant::now();
+            loop {
+                if let Some(pid) = find_game_pid(&mut sys, &target_exe, by_name) {
+                    break pid;
+                }
+                if start.elapsed() > START_TIMEOUT {
+                    println!("[GameSession] {} never showed up, not tracking this session", game_name);
+                    app.state::<GameSessionTracker>().0.lock().unwrap().remove(&game_name);
+                    return;
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    };
+
+    let started_at = now_secs();
+    println!("[GameSession] {} started (pid {})", game_name, pid);
+    let _ = app.emit("game-started", GameStartedEvent { game_name: game_name.clone(), pid: pid.as_u32(), started_at });
+
+    // Post-start hooks run alongside, a slow hook must not delay exit detection
+    if !hooks.post_start.is_empty() {
+        let (app_handle, name, post_start) = (app.clone(), game_name.clone(), hooks.post_start.clone());
+        let env = vec![("SSMT4_GAME_PID", pid.as_u32().to_string())];
+        tauri::async_runtime::spawn(async move {
+            let _ = run_hooks(&app_handle, &name, HookStage::PostStart, &post_start, env).await;
+        });
+    }
+
+    let exit_code = loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        match child.as_mut() {
+            Some(c) => match c.try_wait() {
+                Ok(Some(status)) => break status.code(),
+                Ok(None) => {}
+                Err(_) => break None,
+            },
+            None => {
+                if !sys.refresh_process(pid) {
+                    break None;
+                }
+            }
+        }
+    };
+
+    let ended_at = now_secs();
+    let session = GameSession {
+        started_at,
+        ended_at,
+        duration_seconds: ended_at.saturating_sub(started_at),
+        exit_code,
+    };
+    println!("[GameSession] {} exited after {}s (code {:?})", game_name, session.duration_seconds, exit_code);
+
+    let game_dir = get_global_games_dir(&app).join(&game_name);
+    if let Err(e) = record_session(&game_dir, session.clone()) {
+        println!("[GameSession] {}", e);
+    }
+    let env = vec![
+        ("SSMT4_EXIT_CODE", exit_code.map(|c| c.to_string()).unwrap_or_default()),
+        ("SSMT4_PLAY_SECONDS", session.duration_seconds.to_string()),
+    ];
+    let _ = app.emit("game-exited", GameExitedEvent { game_name: game_name.clone(), exit_code, session });
+    let _ = run_hooks(&app, &game_name, HookStage::PostExit, &hooks.post_exit, env).await;
+    app.state::<GameSessionTracker>().0.lock().unwrap().remove(&game_name);
+}
+
+// by_name: match the process by file name as well (Wine / Proton runners)
+pub(crate) fn track_game_session(app: &AppHandle, game_name: &str, target_exe: &Path, by_name: bool, child: Option<Child>, hooks: LaunchHooks) {
+    let state = app.state::<GameSessionTracker>();
+    let mut sessions = state.0.lock().unwrap();
+    if let Some(previous) = sessions.remove(game_name) {
+        previous.abort();
+    }
+    let handle = tauri::async_runtime::spawn(watch_session(
+        app.clone(),
+        game_name.to_string(),
+        target_exe.to_path_buf(),
+        by_name,
+        child,
+        hooks,
+    ));
+    sessions.insert(game_name.to_string(), handle);
+}
+
+#[tauri::command]
+pub fn get_play_stats(app: AppHandle, game_name: String) -> Result<PlayStats, String> {
+    let game_dir = get_global_games_dir(&app).join(&game_name);
+    if !game_dir.is_dir() {
+        return Err(format!("Game not found: {}", game_name));
+    }
+    Ok(load_play_stats(&game_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET: &str = r"c:\games\genshin impact\genshinimpact.exe";
+
+    #[test]
+    fn matches_by_exe_path() {
+        let exe = PathBuf::from(r"C:\Games\Genshin Impact\GenshinImpact.exe");
+        assert!(is_game_process(Some(&exe), "GenshinImpact.exe", None, TARGET, false));
+        let other = PathBuf::from(r"D:\Other\GenshinImpact.exe");
+        assert!(!is_game_process(Some(&other), "GenshinImpact.exe", None, TARGET, false));
+    }
+
+    #[test]
+    fn falls_back_to_name_when_exe_is_hidden() {
+        // Elevated process seen from an unelevated launcher
+        assert!(is_game_process(None, "GenshinImpact.exe", None, TARGET, false));
+        assert!(!is_game_process(None, "explorer.exe", None, TARGET, false));
+    }
+
+    #[test]
+    fn matches_wine_processes_by_name_or_command_line() {
+        let loader = PathBuf::from("/usr/bin/wine64-preloader");
+        assert!(is_game_process(Some(&loader), "GenshinImpact.", Some(r"C:\Games\GenshinImpact.exe"), TARGET, true));
+        assert!(is_game_process(Some(&loader), "genshinimpact.exe", None, TARGET, true));
+        assert!(!is_game_process(Some(&loader), "GenshinImpact.exe", None, TARGET, false));
+    }
+}
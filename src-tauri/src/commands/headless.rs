@@ -0,0 +1,196 @@
+use crate::commands::deep_link::launcher_exe;
+use crate::commands::game_launcher::start_game;
+use crate::commands::game_sessions::GameSessionTracker;
+use crate::utils::file_manager::get_global_games_dir;
+use crate::utils::mod_paths::check_file_name;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+
+// `ssmt4 --launch <GameName> [--profile <LaunchProfile>]` runs the start_game pipeline
+// without showing the main window. Started that way the app quits once the game
+// session (and its post-exit hooks) is over, forwarded to a running instance it
+// just launches and leaves the UI as it is.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessLaunch {
+    pub game_name: String,
+    pub profile: Option<String>,
+}
+
+// Accepts "--launch Game" and "--launch=Game", same for --profile
+pub fn parse_launch_args(args: &[String]) -> Option<HeadlessLaunch> {
+    let mut game_name = None;
+    let mut profile = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value_of = |flag: &str| -> Option<String> {
+            if arg == flag {
+                iter.next().cloned()
+            } else {
+                arg.strip_prefix(&format!("{}=", flag)).map(|v| v.to_string())
+            }
+        };
+        if let Some(v) = value_of("--launch") {
+            game_name = Some(v);
+        } else if let Some(v) = value_of("--profile") {
+            profile = Some(v);
+        }
+    }
+    let game_name = game_name.map(|g| g.trim().to_string()).filter(|g| !g.is_empty())?;
+    Some(HeadlessLaunch {
+        game_name,
+        profile: profile.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()),
+    })
+}
+
+fn main_window_visible(app: &AppHandle) -> bool {
+    app.get_webview_window("main").and_then(|w| w.is_visible().ok()).unwrap_or(false)
+}
+
+// quit_when_done: exit the app after the session if the UI was never opened
+pub fn run_headless_launch(app: &AppHandle, launch: HeadlessLaunch, quit_when_done: bool) {
+    println!("[Headless] Launching {} (profile: {:?})", launch.game_name, launch.profile);
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let game_name = launch.game_name.clone();
+        if let Err(e) = start_game(app_handle.clone(), launch.game_name, launch.profile).await {
+            println!("[Headless] Launch failed: {}", e);
+            let exit_handle = app_handle.clone();
+            app_handle
+                .dialog()
+                .message(&e)
+                .title(format!("Failed to launch {}", game_name))
+                .kind(MessageDialogKind::Error)
+                .show(move |_| {
+                    if quit_when_done && !main_window_visible(&exit_handle) {
+                        exit_handle.exit(1);
+                    }
+                });
+            return;
+        }
+        if !quit_when_done {
+            return;
+        }
+
+        // Session tracking removes the game once it exits and the hooks have run
+        loop {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            let tracked = app_handle.state::<GameSessionTracker>().0.lock().unwrap().contains_key(&game_name);
+            if !tracked {
+                break;
+            }
+        }
+        if !main_window_visible(&app_handle) {
+            println!("[Headless] {} finished, exiting", game_name);
+            app_handle.exit(0);
+        }
+    });
+}
+
+fn desktop_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().desktop_dir().map_err(|e| format!("Failed to find the desktop folder: {}", e))
+}
+
+// Quote one argument for a .desktop Exec line
+#[cfg(not(windows))]
+pub(crate) fn desktop_exec_quote(arg: &str) -> String {
+    if !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:".contains(c)) {
+        return arg.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for c in arg.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    // The whole Exec value goes through the desktop entry string unescaping as well
+    quoted.replace('\\', "\\\\").replace('%', "%%")
+}
+
+#[cfg(not(windows))]
+fn write_shortcut(app: &AppHandle, dir: &std::path::Path, name: &str, exe: &std::path::Path, args: &[String], game_name: &str) -> Result<PathBuf, String> {
+    let path = dir.join(format!("{}.desktop", name));
+    let exec: Vec<String> = std::iter::once(exe.to_string_lossy().to_string())
+        .chain(args.iter().cloned())
+        .map(|a| desktop_exec_quote(&a))
+        .collect();
+    let icon = get_global_games_dir(app).join(game_name).join("Icon.png");
+
+    let mut content = format!(
+        "[Desktop Entry]\nType=Application\nName={}\nComment=Launch {} with mods\nExec={}\nTerminal=false\nCategories=Game;\n",
+        name.replace('\n', " "),
+        game_name.replace('\n', " "),
+        exec.join(" ")
+    );
+    if icon.is_file() {
+        content.push_str(&format!("Icon={}\n", icon.to_string_lossy()));
+    }
+    fs::write(&path, content).map_err(|e| format!("Failed to write shortcut: {}", e))?;
+
+    // Desktops only run launchers that are executable
+    use std::os::unix::fs::PermissionsExt;
+    let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o755));
+    Ok(path)
+}
+
+#[cfg(windows)]
+fn write_shortcut(_app: &AppHandle, dir: &std::path::Path, name: &str, exe: &std::path::Path, args: &[String], _game_name: &str) -> Result<PathBuf, String> {
+    let path = dir.join(format!("{}.lnk", name));
+    let arguments: Vec<String> = args.iter().map(|a| crate::utils::platform::quote_windows_arg(a)).collect();
+
+    // Values go in through the environment, nothing user supplied ends up in the script text
+    let script = "$s = (New-Object -ComObject WScript.Shell).CreateShortcut($env:SSMT4_LNK_PATH); \
+                  $s.TargetPath = $env:SSMT4_LNK_TARGET; $s.Arguments = $env:SSMT4_LNK_ARGS; \
+                  $s.WorkingDirectory = $env:SSMT4_LNK_DIR; $s.IconLocation = $env:SSMT4_LNK_TARGET; $s.Save()";
+    let output = crate::utils::platform::hidden_command("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .env("SSMT4_LNK_PATH", &path)
+        .env("SSMT4_LNK_TARGET", exe)
+        .env("SSMT4_LNK_ARGS", arguments.join(" "))
+        .env("SSMT4_LNK_DIR", exe.parent().unwrap_or(exe))
+        .output()
+        .map_err(|e| format!("Failed to run powershell: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Failed to create shortcut: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(path)
+}
+
+// Desktop icon that launches the game modded without opening the UI. Returns the shortcut path.
+#[tauri::command]
+pub fn create_game_shortcut(
+    app: AppHandle,
+    game_name: String,
+    profile: Option<String>,
+    target_dir: Option<String>,
+) -> Result<String, String> {
+    check_file_name(&game_name)?;
+    if !get_global_games_dir(&app).join(&game_name).join("Config.json").exists() {
+        return Err(format!("Game config not found: {}", game_name));
+    }
+    let dir = match target_dir.filter(|d| !d.trim().is_empty()) {
+        Some(d) => PathBuf::from(d),
+        None => desktop_dir(&app)?,
+    };
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+
+    let exe = launcher_exe()?;
+    let mut args = vec!["--launch".to_string(), game_name.clone()];
+    let mut name = game_name.clone();
+    if let Some(p) = profile.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()) {
+        name = format!("{} ({})", game_name, p);
+        args.push("--profile".to_string());
+        args.push(p);
+    }
+    // Profile names are free text, keep the file name valid
+    let name: String = name.chars().map(|c| if "<>:\"/\\|?*".contains(c) || c.is_control() { '_' } else { c }).collect();
+
+    let path = write_shortcut(&app, &dir, &name, &exe, &args, &game_name)?;
+    println!("[Headless] Created shortcut {:?}", path);
+    Ok(path.to_string_lossy().to_string())
+}
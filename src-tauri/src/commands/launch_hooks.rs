@@ -0,0 +1,304 @@
+use crate::utils::platform::hidden_command;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+// User commands around a launch ("hooks" in the threeDMigoto config):
+//   preLaunch  - before boot files, UPX, d3dx.ini and Run.exe, may abort the launch
+//   postStart  - once the game process shows up
+//   postExit   - after the game exits
+// Each run is reported through "launch-hook-finished" with its captured output.
+
+const DEFAULT_HOOK_TIMEOUT_SECONDS: u64 = 60;
+const MAX_CAPTURED_OUTPUT: usize = 64 * 1024;
+// How long to keep reading output once the hook itself is gone, processes it started may hold the pipes
+const OUTPUT_GRACE: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchHooks {
+    #[serde(default)]
+    pub pre_launch: Vec<LaunchHook>,
+    #[serde(default)]
+    pub post_start: Vec<LaunchHook>,
+    #[serde(default)]
+    pub post_exit: Vec<LaunchHook>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchHook {
+    #[serde(default)]
+    pub name: String,
+    pub command: String, // Program or script (.bat / .sh), not a shell line
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub working_dir: Option<String>, // Defaults to the command's folder
+    pub timeout_seconds: Option<u64>,
+    #[serde(default)]
+    pub abort_on_failure: bool, // Only meaningful for preLaunch
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum HookStage {
+    PreLaunch,
+    PostStart,
+    PostExit,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HookResult {
+    pub game_name: String,
+    pub stage: HookStage,
+    pub name: String,
+    pub command: String,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub duration_ms: u64,
+    pub stdout: String,
+    pub stderr: String,
+    pub error: Option<String>, // Could not start at all
+}
+
+// Collects a pipe into a shared buffer (last MAX_CAPTURED_OUTPUT bytes) until EOF
+struct OutputReader {
+    buf: Arc<Mutex<Vec<u8>>>,
+    done: mpsc::Receiver<()>,
+}
+
+impl OutputReader {
+    fn spawn(mut pipe: impl Read + Send + 'static) -> Self {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let (tx, done) = mpsc::channel();
+        let shared = buf.clone();
+        std::thread::spawn(move || {
+            let mut chunk = [0u8; 8192];
+            while let Ok(n) = pipe.read(&mut chunk) {
+                if n == 0 {
+                    break;
+                }
+                let mut buf = shared.lock().unwrap_or_else(|e| e.into_inner());
+                buf.extend_from_slice(&chunk[..n]);
+                if buf.len() > MAX_CAPTURED_OUTPUT {
+                    let excess = buf.len() - MAX_CAPTURED_OUTPUT;
+                    buf.drain(..excess);
+                }
+            }
+            let _ = tx.send(());
+        });
+        Self { buf, done }
+    }
+
+    // Whatever was read by the deadline, a leftover background process must not hang the launch
+    fn finish(self, deadline: Instant) -> String {
+        let _ = self.done.recv_timeout(deadline.saturating_duration_since(Instant::now()));
+        let buf = self.buf.lock().unwrap_or_else(|e| e.into_inner());
+        String::from_utf8_lossy(&buf).to_string()
+    }
+}
+
+// The hook runs in its own process group on Unix (taskkill /T walks the tree on Windows),
+// take everything it started down with it
+fn kill_process_tree(child: &mut Child) {
+    let pid = child.id().to_string();
+    #[cfg(windows)]
+    let _ = hidden_command("taskkill").args(["/T", "/F", "/PID", &pid]).output();
+    #[cfg(unix)]
+    let _ = hidden_command("kill").args(["-KILL", "--", &format!("-{}", pid)]).output();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> (Option<i32>, bool) {
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return (status.code(), false),
+            Ok(None) if start.elapsed() >= timeout => {
+                kill_process_tree(child);
+                return (None, true);
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+            Err(_) => return (None, false),
+        }
+    }
+}
+
+fn run_hook(game_name: &str, stage: HookStage, hook: &LaunchHook, extra_env: &[(&str, String)]) -> HookResult {
+    let mut result = HookResult {
+        game_name: game_name.to_string(),
+        stage,
+        name: if hook.name.is_empty() { hook.command.clone() } else { hook.name.clone() },
+        command: hook.command.clone(),
+        success: false,
+        exit_code: None,
+        timed_out: false,
+        duration_ms: 0,
+        stdout: String::new(),
+        stderr: String::new(),
+        error: None,
+    };
+
+    let command_path = Path::new(hook.command.trim());
+    let work_dir = match hook.working_dir.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        Some(dir) => Some(Path::new(dir).to_path_buf()),
+        None => command_path.parent().filter(|p| p.is_dir()).map(|p| p.to_path_buf()),
+    };
+
+    let mut cmd = hidden_command(command_path);
+    cmd.args(&hook.args)
+        .env("SSMT4_GAME", game_name)
+        .env("SSMT4_HOOK_STAGE", format!("{:?}", stage))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    for (key, value) in extra_env {
+        cmd.env(key, value);
+    }
+    if let Some(dir) = &work_dir {
+        cmd.current_dir(dir);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+
+    let start = Instant::now();
+    let mut child = match cmd.spawn() {
+        Ok(c) => c,
+        Err(e) => {
+            result.error = Some(format!("Failed to start {}: {}", hook.command, e));
+            return result;
+        }
+    };
+
+    // Drain both pipes on their own threads so a chatty hook cannot block on a full pipe
+    let stdout = child.stdout.take().map(OutputReader::spawn);
+    let stderr = child.stderr.take().map(OutputReader::spawn);
+
+    let timeout = Duration::from_secs(hook.timeout_seconds.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECONDS).max(1));
+    let (exit_code, timed_out) = wait_with_timeout(&mut child, timeout);
+
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    result.exit_code = exit_code;
+    result.timed_out = timed_out;
+    result.success = !timed_out && exit_code == Some(0);
+    let deadline = Instant::now() + OUTPUT_GRACE;
+    result.stdout = stdout.map(|r| r.finish(deadline)).unwrap_or_default();
+    result.stderr = stderr.map(|r| r.finish(deadline)).unwrap_or_default();
+    result
+}
+
+// Err when this failed hook should stop the launch
+fn check_abort(stage: HookStage, hook: &LaunchHook, result: &HookResult) -> Result<(), String> {
+    if result.success || !hook.abort_on_failure || stage != HookStage::PreLaunch {
+        return Ok(());
+    }
+    let reason = result
+        .error
+        .clone()
+        .unwrap_or_else(|| if result.timed_out { "timed out".to_string() } else { format!("exit code {:?}", result.exit_code) });
+    Err(format!("Launch aborted: hook '{}' failed ({})", result.name, reason))
+}
+
+// Run a stage's hooks in order. Err when a hook with abortOnFailure fails.
+pub(crate) async fn run_hooks(
+    app: &AppHandle,
+    game_name: &str,
+    stage: HookStage,
+    hooks: &[LaunchHook],
+    extra_env: Vec<(&'static str, String)>,
+) -> Result<Vec<HookResult>, String> {
+    let mut results = Vec::new();
+    for hook in hooks.iter().filter(|h| h.enabled && !h.command.trim().is_empty()) {
+        println!("[LaunchHooks] {:?} hook for {}: {} {:?}", stage, game_name, hook.command, hook.args);
+        let (game, hook_owned, env) = (game_name.to_string(), hook.clone(), extra_env.clone());
+        let result = tauri::async_runtime::spawn_blocking(move || run_hook(&game, stage, &hook_owned, &env))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        println!(
+            "[LaunchHooks] {} finished: success={}, exit={:?}, timed out={}",
+            result.name, result.success, result.exit_code, result.timed_out
+        );
+        let _ = app.emit("launch-hook-finished", &result);
+
+        let abort = check_abort(stage, hook, &result);
+        results.push(result);
+        abort?;
+    }
+    Ok(results)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn sh_hook(script: &str, timeout_seconds: Option<u64>) -> LaunchHook {
+        LaunchHook {
+            name: "test".to_string(),
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            working_dir: None,
+            timeout_seconds,
+            abort_on_failure: true,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn captures_output_and_exit_code() {
+        let hook = sh_hook("echo out; echo err >&2; echo $SSMT4_GAME; exit 3", None);
+        let result = run_hook("Game", HookStage::PreLaunch, &hook, &[]);
+        assert_eq!(result.stdout, "out\nGame\n");
+        assert_eq!(result.stderr, "err\n");
+        assert_eq!(result.exit_code, Some(3));
+        assert!(!result.success && !result.timed_out);
+    }
+
+    #[test]
+    fn timeout_kills_processes_holding_the_pipes() {
+        let hook = sh_hook("echo started; sleep 30 & sleep 30", Some(1));
+        let start = Instant::now();
+        let result = run_hook("Game", HookStage::PreLaunch, &hook, &[]);
+        assert!(result.timed_out && !result.success);
+        assert_eq!(result.stdout, "started\n");
+        assert!(start.elapsed() < Duration::from_secs(10), "took {:?}", start.elapsed());
+    }
+
+    #[test]
+    fn background_processes_do_not_block_a_finished_hook() {
+        let hook = sh_hook("sleep 30 & echo done", None);
+        let start = Instant::now();
+        let result = run_hook("Game", HookStage::PostStart, &hook, &[]);
+        assert!(result.success);
+        assert_eq!(result.stdout, "done\n");
+        assert!(start.elapsed() < Duration::from_secs(10), "took {:?}", start.elapsed());
+    }
+
+    #[test]
+    fn only_failed_pre_launch_hooks_abort() {
+        let mut hook = sh_hook("exit 1", None);
+        let failed = run_hook("Game", HookStage::PreLaunch, &hook, &[]);
+        assert!(check_abort(HookStage::PreLaunch, &hook, &failed).unwrap_err().contains("exit code Some(1)"));
+        assert!(check_abort(HookStage::PostExit, &hook, &failed).is_ok());
+        hook.abort_on_failure = false;
+        assert!(check_abort(HookStage::PreLaunch, &hook, &failed).is_ok());
+
+        let passed = run_hook("Game", HookStage::PreLaunch, &sh_hook("true", None), &[]);
+        assert!(check_abort(HookStage::PreLaunch, &hook, &passed).is_ok());
+    }
+}
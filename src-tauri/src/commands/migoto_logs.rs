@@ -0,0 +1,355 @@
+use crate::commands::mod_linter::LintSeverity;
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth, scan_folder, ScanState};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+// Reads 3DMigoto's own logs (d3d11_log.txt and friends in the install dir) and turns
+// warnings into diagnostics pointing at the mod that caused them. After a launch the
+// logs are tailed for a while and new findings are emitted as "migoto-diagnostics".
+
+const MAIN_LOG: &str = "d3d11_log.txt";
+const TAIL_DURATION: Duration = Duration::from_secs(600);
+const TAIL_INTERVAL: Duration = Duration::from_secs(2);
+const HEAD_BYTES: usize = 256;
+
+// One tail per game, a new launch replaces the previous one
+pub struct MigotoLogTailer(pub Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum MigotoDiagnosticKind {
+    IniParse,
+    MissingResource,
+    Injection,
+    Shader,
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MigotoDiagnostic {
+    pub kind: MigotoDiagnosticKind,
+    pub severity: LintSeverity,
+    pub message: String,
+    pub log_file: String,
+    pub log_line: usize,
+    pub file: Option<String>,              // File under Mods the message refers to
+    pub mod_relative_path: Option<String>, // Mod folder containing that file
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct MigotoDiagnosticsEvent {
+    game_name: String,
+    diagnostics: Vec<MigotoDiagnostic>,
+}
+
+fn log_files(install_dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(install_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_file())
+                .filter(|p| {
+                    let name = p.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+                    name.ends_with("_log.txt")
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    // Main log first, the rest by name
+    files.sort_by_key(|p| {
+        let name = p.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+        (name != MAIN_LOG, name)
+    });
+    files
+}
+
+fn classify(line: &str) -> Option<(MigotoDiagnosticKind, LintSeverity)> {
+    let lower = line.to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| lower.contains(w));
+
+    if !has(&["warning", "error", "fail", "unable", "not found", "cannot", "could not"]) {
+        return None;
+    }
+    let failed = has(&["fail", "error", "unable", "cannot", "could not"]);
+
+    if has(&["inject", "hook", "loader"]) && failed {
+        Some((MigotoDiagnosticKind::Injection, LintSeverity::Error))
+    } else if has(&["not found", "unable to find", "could not find", "cannot find", "failed to load", "unable to load", "0x80070002", "0x80070003"]) {
+        Some((MigotoDiagnosticKind::MissingResource, LintSeverity::Error))
+    } else if lower.contains("shader") && failed {
+        Some((MigotoDiagnosticKind::Shader, LintSeverity::Error))
+    } else if has(&["unrecognised", "unrecognized", "unknown", "duplicate", "invalid", "parse", "syntax"]) {
+        Some((MigotoDiagnosticKind::IniParse, LintSeverity::Warning))
+    } else if failed {
+        Some((MigotoDiagnosticKind::Other, LintSeverity::Error))
+    } else {
+        Some((MigotoDiagnosticKind::Other, LintSeverity::Warning))
+    }
+}
+
+// "...Mods\Group\My Mod\Textures\a.dds..." -> "Group/My Mod/Textures/a.dds"
+fn extract_mods_path(line: &str) -> Option<String> {
+    let lower = line.to_ascii_lowercase();
+    // "...\GIMods\..." is not the Mods folder
+    let start = ["mods\\", "mods/"]
+        .iter()
+        .filter_map(|m| {
+            lower
+                .match_indices(m)
+                .map(|(i, _)| i)
+                .find(|&i| lower[..i].chars().next_back().map(|c| !c.is_ascii_alphanumeric()).unwrap_or(true))
+        })
+        .min()?
+        + 5;
+    let rest = &lower[start..];
+
+    let end = [".ini", ".dds", ".buf", ".ib", ".vb", ".png", ".jpg", ".hlsl", ".txt"]
+        .iter()
+        .filter_map(|ext| {
+            rest.match_indices(ext)
+                .map(|(i, _)| i + ext.len())
+                .find(|&i| rest[i..].chars().next().map(|c| !c.is_ascii_alphanumeric()).unwrap_or(true))
+        })
+        .min()?;
+    Some(line[start..start + end].replace('\\', "/"))
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/").trim_matches('/').to_lowercase()
+}
+
+// Longest mod relative path that contains the file
+fn owning_mod(file: &str, mod_paths: &[String]) -> Option<String> {
+    let file = normalize(file);
+    mod_paths
+        .iter()
+        .filter(|m| {
+            let m = normalize(m);
+            file.starts_with(&m) && file[m.len()..].starts_with('/')
+        })
+        .max_by_key(|m| m.len())
+        .cloned()
+}
+
+pub(crate) fn parse_migoto_log(content: &str, first_line: usize, log_file: &str, mod_paths: &[String]) -> Vec<MigotoDiagnostic> {
+    let mut diagnostics = Vec::new();
+    // 3DMigoto logs the ini it is reading, warnings without a path belong to that file
+    let mut current_file: Option<String> = None;
+
+    for (i, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let path = extract_mods_path(line);
+        let kind = classify(line);
+
+        let (kind, severity) = match kind {
+            Some(k) => k,
+            None => {
+                if let Some(p) = path.filter(|p| p.to_lowercase().ends_with(".ini")) {
+                    current_file = Some(p);
+                }
+                continue;
+            }
+        };
+
+        let file = path.or_else(|| current_file.clone());
+        diagnostics.push(MigotoDiagnostic {
+            kind,
+            severity,
+            message: line.to_string(),
+            log_file: log_file.to_string(),
+            log_line: first_line + i,
+            mod_relative_path: file.as_deref().and_then(|f| owning_mod(f, mod_paths)),
+            file,
+        });
+    }
+    diagnostics
+}
+
+fn mod_relative_paths(app: &AppHandle, game_name: &str, install_dir: &Path) -> Vec<String> {
+    let mods_dir = install_dir.join("Mods");
+    let mut state = ScanState::new(get_mod_scan_depth(app, game_name));
+    scan_folder(&mods_dir, &mods_dir, "Root".to_string(), 0, false, &mut state);
+    state.mods.into_iter().map(|m| m.relative_path).collect()
+}
+
+fn read_lossy(path: &Path) -> String {
+    fs::read(path).map(|b| String::from_utf8_lossy(&b).to_string()).unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_3dmigoto_diagnostics(app: AppHandle, game_name: String) -> Result<Vec<MigotoDiagnostic>, String> {
+    let install_dir = get_game_install_dir(&app, &game_name)?;
+    let mod_paths = mod_relative_paths(&app, &game_name, &install_dir);
+
+    Ok(log_files(&install_dir)
+        .iter()
+        .flat_map(|log| {
+            let name = log.file_name().unwrap_or_default().to_string_lossy().to_string();
+            parse_migoto_log(&read_lossy(log), 1, &name, &mod_paths)
+        })
+        .collect())
+}
+
+struct TailedLog {
+    offset: u64,
+    line: usize,
+    head: Vec<u8>, // 3DMigoto rewrites its log on every start, a new head means a new file
+}
+
+fn read_head(path: &Path) -> Vec<u8> {
+    let mut head = vec![0u8; HEAD_BYTES];
+    let n = fs::File::open(path).and_then(|mut f| f.read(&mut head)).unwrap_or(0);
+    head.truncate(n);
+    head
+}
+
+fn read_new_lines(path: &Path, state: &mut TailedLog) -> Option<(String, usize)> {
+    let len = fs::metadata(path).ok()?.len();
+    let head = read_head(path);
+    if len < state.offset || !head.starts_with(&state.head[..state.head.len().min(head.len())]) {
+        state.offset = 0;
+        state.line = 1;
+    }
+    state.head = head;
+    if len == state.offset {
+        return None;
+    }
+
+    let mut file = fs::File::open(path).ok()?;
+    file.seek(SeekFrom::Start(state.offset)).ok()?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).ok()?;
+    // Only hand over complete lines, the rest is read next time
+    let complete = buf.iter().rposition(|&b| b == b'\n').map(|i| i + 1)?;
+    buf.truncate(complete);
+    state.offset += complete as u64;
+
+    let text = String::from_utf8_lossy(&buf).to_string();
+    let first_line = state.line;
+    state.line += text.lines().count();
+    Some((text, first_line))
+}
+
+async fn tail_logs(app: AppHandle, game_name: String, install_dir: PathBuf) {
+    // Whatever is in the logs now is from the previous run
+    let mut tailed: HashMap<PathBuf, TailedLog> = log_files(&install_dir)
+        .into_iter()
+        .map(|p| {
+            let offset = fs::metadata(&p).map(|m| m.len()).unwrap_or(0);
+            let line = read_lossy(&p).lines().count() + 1;
+            let head = read_head(&p);
+            (p, TailedLog { offset, line, head })
+        })
+        .collect();
+    let mod_paths = mod_relative_paths(&app, &game_name, &install_dir);
+
+    let start = std::time::Instant::now();
+    while start.elapsed() < TAIL_DURATION {
+        tokio::time::sleep(TAIL_INTERVAL).await;
+
+        let mut diagnostics = Vec::new();
+        for log in log_files(&install_dir) {
+            let state = tailed.entry(log.clone()).or_insert(TailedLog { offset: 0, line: 1, head: Vec::new() });
+            if let Some((text, first_line)) = read_new_lines(&log, state) {
+                let name = log.file_name().unwrap_or_default().to_string_lossy().to_string();
+                diagnostics.extend(parse_migoto_log(&text, first_line, &name, &mod_paths));
+            }
+        }
+        if !diagnostics.is_empty() {
+            println!("[MigotoLog] {} new diagnostic(s) for {}", diagnostics.len(), game_name);
+            let _ = app.emit("migoto-diagnostics", MigotoDiagnosticsEvent { game_name: game_name.clone(), diagnostics });
+        }
+    }
+    app.state::<MigotoLogTailer>().0.lock().unwrap().remove(&game_name);
+}
+
+pub(crate) fn tail_3dmigoto_logs(app: &AppHandle, game_name: &str, install_dir: &Path) {
+    let state = app.state::<MigotoLogTailer>();
+    let mut tails = state.0.lock().unwrap();
+    if let Some(previous) = tails.remove(game_name) {
+        previous.abort();
+    }
+    let handle = tauri::async_runtime::spawn(tail_logs(app.clone(), game_name.to_string(), install_dir.to_path_buf()));
+    tails.insert(game_name.to_string(), handle);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::TempDir;
+
+    #[test]
+    fn classifies_log_lines() {
+        assert_eq!(classify("  Loading shader 1234"), None);
+        assert_eq!(classify("Failed to inject into process"), Some((MigotoDiagnosticKind::Injection, LintSeverity::Error)));
+        assert_eq!(classify("WARNING: Resource file not found: a.dds"), Some((MigotoDiagnosticKind::MissingResource, LintSeverity::Error)));
+        assert_eq!(classify("Error compiling shader"), Some((MigotoDiagnosticKind::Shader, LintSeverity::Error)));
+        assert_eq!(classify("WARNING: Unrecognised entry: foo=bar"), Some((MigotoDiagnosticKind::IniParse, LintSeverity::Warning)));
+        assert_eq!(classify("warning: something odd"), Some((MigotoDiagnosticKind::Other, LintSeverity::Warning)));
+    }
+
+    #[test]
+    fn extracts_paths_under_mods() {
+        assert_eq!(
+            extract_mods_path(r"Loading D:\3dm\MODS\Group\My Mod\Body.ini..."),
+            Some("Group/My Mod/Body.ini".to_string())
+        );
+        assert_eq!(extract_mods_path("not found: Mods/A/tex.dds, skipping"), Some("A/tex.dds".to_string()));
+        // ".ini" inside a folder name is not the extension
+        assert_eq!(extract_mods_path(r"Mods\A.inis\b.dds"), Some("A.inis/b.dds".to_string()));
+        assert_eq!(extract_mods_path(r"D:\GIMods\Mods\A\a.ini"), Some("A/a.ini".to_string()));
+        assert_eq!(extract_mods_path(r"D:\GIMods\a.ini"), None);
+        assert_eq!(extract_mods_path(r"Mods\A\readme"), None);
+    }
+
+    #[test]
+    fn picks_the_longest_owning_mod() {
+        let mods = vec!["Group/A".to_string(), "Group/A/Variant".to_string(), "Group/AB".to_string()];
+        assert_eq!(owning_mod("group/a/variant/x.dds", &mods), Some("Group/A/Variant".to_string()));
+        assert_eq!(owning_mod("Group/A/x.dds", &mods), Some("Group/A".to_string()));
+        assert_eq!(owning_mod("Group/ABC/x.dds", &mods), None);
+    }
+
+    #[test]
+    fn warnings_without_a_path_belong_to_the_current_ini() {
+        let log = "Loading Mods\\Group\\A\\a.ini\nWARNING: Unrecognised entry: foo\n\nResource not found: Mods\\Group\\B\\b.dds\n";
+        let mods = vec!["Group/A".to_string(), "Group/B".to_string()];
+        let diagnostics = parse_migoto_log(log, 10, MAIN_LOG, &mods);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].log_line, 11);
+        assert_eq!(diagnostics[0].file.as_deref(), Some("Group/A/a.ini"));
+        assert_eq!(diagnostics[0].mod_relative_path.as_deref(), Some("Group/A"));
+        assert_eq!(diagnostics[1].log_line, 13);
+        assert_eq!(diagnostics[1].mod_relative_path.as_deref(), Some("Group/B"));
+    }
+
+    #[test]
+    fn tails_complete_lines_and_detects_rewrites() {
+        let dir = TempDir::new("migoto_log_tail");
+        let log = dir.write(MAIN_LOG, "first run\nline 2\n");
+        let mut state = TailedLog { offset: 0, line: 1, head: Vec::new() };
+        assert_eq!(read_new_lines(&log, &mut state), Some(("first run\nline 2\n".to_string(), 1)));
+        assert_eq!(read_new_lines(&log, &mut state), None);
+
+        // Partial lines wait for their newline
+        fs::write(&log, "first run\nline 2\nline 3\npart").unwrap();
+        assert_eq!(read_new_lines(&log, &mut state), Some(("line 3\n".to_string(), 3)));
+        fs::write(&log, "first run\nline 2\nline 3\npartial\n").unwrap();
+        assert_eq!(read_new_lines(&log, &mut state), Some(("partial\n".to_string(), 4)));
+
+        // A new launch rewrites the log, even when it ends up longer than before
+        fs::write(&log, "second run, a much longer first line\nx\ny\n").unwrap();
+        assert_eq!(read_new_lines(&log, &mut state), Some(("second run, a much longer first line\nx\ny\n".to_string(), 1)));
+    }
+}
@@ -0,0 +1,441 @@
+use crate::commands::mod_linter::{LintIssue, LintSeverity};
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth};
+use crate::utils::mod_ini::{collect_mod_inis, ModIni, ModIniFile};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+// Checks the [Resource*] buffers of mesh mods against the data on disk:
+//   - file size is a multiple of the stride (or index size)
+//   - every index used by a drawindexed range points at an existing vertex
+//   - drawindexed / draw ranges fit inside the index / vertex buffer
+// A mesh exported with the wrong stride loads fine and then crashes the game on draw.
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum BufferKind {
+    Vertex,
+    Index,
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BufferInfo {
+    pub resource: String,
+    pub ini_path: String,
+    pub file: String,
+    pub kind: BufferKind,
+    pub stride: u32,        // Bytes per element (vertex or index)
+    pub format: Option<String>,
+    pub size_bytes: u64,
+    pub element_count: u64, // Vertices or indices
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModBufferReport {
+    pub mod_relative_path: String,
+    pub buffers: Vec<BufferInfo>,
+    pub issues: Vec<LintIssue>,
+}
+
+fn format_size(format: &str) -> Option<u32> {
+    let format = format.trim().to_uppercase();
+    let format = format.trim_start_matches("DXGI_FORMAT_");
+    match format {
+        "R16_UINT" | "R16_SINT" | "R16_UNORM" | "R16_FLOAT" => Some(2),
+        "R32_UINT" | "R32_SINT" | "R32_FLOAT" | "R8G8B8A8_UNORM" | "R8G8B8A8_UINT" | "R16G16_FLOAT" => Some(4),
+        "R32G32_FLOAT" | "R16G16B16A16_FLOAT" | "R16G16B16A16_UNORM" => Some(8),
+        "R32G32B32_FLOAT" => Some(12),
+        "R32G32B32A32_FLOAT" | "R32G32B32A32_UINT" => Some(16),
+        _ => None,
+    }
+}
+
+fn parse_numbers(value: &str) -> Vec<i64> {
+    value
+        .split(',')
+        .map(|part| part.trim().parse::<i64>())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_default()
+}
+
+fn resource_name(value: &str) -> Option<String> {
+    // "ResourceFoo", "ref ResourceFoo", "copy ResourceFoo"
+    value
+        .split_whitespace()
+        .last()
+        .filter(|t| t.to_lowercase().starts_with("resource") && !t.contains('\\'))
+        .map(|t| t.to_lowercase())
+}
+
+fn is_vb_slot(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key.len() > 2 && key.starts_with("vb") && key[2..].chars().all(|c| c.is_ascii_digit())
+}
+
+struct LoadedBuffer {
+    info: BufferInfo,
+    indices: Option<Vec<u32>>,
+    line: usize,
+}
+
+fn read_indices(path: &Path, index_size: u32) -> Option<Vec<u32>> {
+    let bytes = fs::read(path).ok()?;
+    let indices = match index_size {
+        2 => bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]]) as u32).collect(),
+        4 => bytes.chunks_exact(4).map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect(),
+        _ => return None,
+    };
+    Some(indices)
+}
+
+fn validate_ini(ini_path: &Path, buffers: &mut Vec<BufferInfo>, issues: &mut Vec<LintIssue>) {
+    let file = ini_path.to_string_lossy().to_string();
+    let mut issue = |line: usize, severity: LintSeverity, code: &str, message: String| {
+        issues.push(LintIssue { file: file.clone(), line, severity, code: code.to_string(), message });
+    };
+
+    let ini = match ModIni::load(ini_path) {
+        Ok(ini) => ini,
+        Err(e) => {
+            issue(0, LintSeverity::Error, "unreadable", e);
+            return;
+        }
+    };
+    let ini_dir = ini_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+
+    // How each resource is bound decides whether it is a vertex or index buffer
+    let mut bound_as_ib = Vec::new();
+    let mut bound_as_vb = Vec::new();
+    for section in &ini.sections {
+        for entry in &section.entries {
+            if let Some(name) = resource_name(&entry.value) {
+                if entry.key.eq_ignore_ascii_case("ib") {
+                    bound_as_ib.push(name);
+                } else if is_vb_slot(&entry.key) {
+                    bound_as_vb.push(name);
+                }
+            }
+        }
+    }
+
+    let mut loaded: HashMap<String, LoadedBuffer> = HashMap::new();
+    for section in ini.sections_with_prefix("Resource") {
+        let is_buffer = section
+            .get("type")
+            .map(|t| t.to_lowercase().contains("buffer"))
+            .unwrap_or(false);
+        let filename = match section.get("filename") {
+            Some(f) if is_buffer => f.trim_matches('"').replace('\\', "/"),
+            _ => continue,
+        };
+
+        // .buf is used for every kind of buffer (blend, texcoord, shape keys bound as t slots),
+        // only the ib / vbN binding or an explicit .ib / .vb says what it is
+        let lower_name = section.name.to_lowercase();
+        let extension = filename.rsplit('.').next().unwrap_or("").to_lowercase();
+        let kind = if bound_as_ib.contains(&lower_name) {
+            BufferKind::Index
+        } else if bound_as_vb.contains(&lower_name) {
+            BufferKind::Vertex
+        } else if extension == "ib" {
+            BufferKind::Index
+        } else if extension == "vb" {
+            BufferKind::Vertex
+        } else {
+            BufferKind::Other
+        };
+
+        let format = section.get("format").map(|f| f.to_string());
+        let stride = section
+            .get("stride")
+            .and_then(|s| s.trim().parse::<u32>().ok())
+            .or_else(|| format.as_deref().and_then(format_size))
+            .unwrap_or(0);
+
+        let path: PathBuf = ini_dir.join(&filename);
+        let size_bytes = match fs::metadata(&path) {
+            Ok(m) => m.len(),
+            Err(_) => {
+                issue(section.line, LintSeverity::Error, "missing-file", format!("[{}] buffer file {} does not exist", section.name, filename));
+                continue;
+            }
+        };
+
+        if stride == 0 {
+            issue(section.line, LintSeverity::Warning, "missing-stride", format!("[{}] has neither stride= nor a known format=", section.name));
+        } else if size_bytes % stride as u64 != 0 {
+            issue(
+                section.line,
+                LintSeverity::Error,
+                "stride-mismatch",
+                format!("[{}] {} is {} bytes, not a multiple of stride {} (wrong export stride?)", section.name, filename, size_bytes, stride),
+            );
+        }
+
+        let indices = if kind == BufferKind::Index && (stride == 2 || stride == 4) {
+            read_indices(&path, stride)
+        } else {
+            None
+        };
+
+        let info = BufferInfo {
+            resource: section.name.clone(),
+            ini_path: file.clone(),
+            file: path.to_string_lossy().to_string(),
+            kind,
+            stride,
+            format,
+            size_bytes,
+            element_count: if stride > 0 { size_bytes / stride as u64 } else { 0 },
+        };
+        loaded.insert(lower_name, LoadedBuffer { info, indices, line: section.line });
+    }
+
+    // Vertex count shared by all vertex buffers of this ini, if they agree
+    let mut vertex_counts: Vec<u64> = loaded
+        .values()
+        .filter(|b| b.info.kind == BufferKind::Vertex && b.info.element_count > 0)
+        .map(|b| b.info.element_count)
+        .collect();
+    vertex_counts.sort();
+    vertex_counts.dedup();
+    let shared_vertex_count = if vertex_counts.len() == 1 { Some(vertex_counts[0]) } else { None };
+
+    for section in &ini.sections {
+        let mut section_vb0 = None;
+        let mut current_ib: Option<&LoadedBuffer> = None;
+
+        for entry in &section.entries {
+            let key = entry.key.to_lowercase();
+            if key == "vb0" {
+                section_vb0 = resource_name(&entry.value).and_then(|n| loaded.get(&n)).map(|b| b.info.element_count);
+            } else if key == "ib" {
+                current_ib = resource_name(&entry.value).and_then(|n| loaded.get(&n));
+            } else if key == "draw" {
+                // draw = vertex_count, start_vertex
+                let numbers = parse_numbers(&entry.value);
+                if numbers.iter().any(|n| *n < 0) {
+                    issue(entry.line, LintSeverity::Error, "draw-out-of-range", format!("draw = {} has a negative argument", entry.value.trim()));
+                    continue;
+                }
+                if let (Some(vertices), [count, start, ..]) = (section_vb0.or(shared_vertex_count), numbers.as_slice()) {
+                    match count.checked_add(*start) {
+                        None => issue(entry.line, LintSeverity::Error, "draw-out-of-range", format!("draw = {}, {} overflows", count, start)),
+                        Some(end) if end > vertices as i64 => {
+                            issue(entry.line, LintSeverity::Error, "draw-out-of-range", format!("draw = {}, {} exceeds the {} vertices in the vertex buffer", count, start, vertices));
+                        }
+                        Some(_) => {}
+                    }
+                }
+            } else if key == "drawindexed" {
+                // drawindexed = index_count, start_index, base_vertex ("auto" is resolved by 3DMigoto)
+                let numbers = parse_numbers(&entry.value);
+                let (count, start, base) = match numbers.as_slice() {
+                    [count, start, base, ..] => (*count, *start, *base),
+                    [count, start] => (*count, *start, 0),
+                    _ => continue,
+                };
+                if start < 0 || count < 0 {
+                    issue(entry.line, LintSeverity::Error, "drawindexed-out-of-range", format!("drawindexed = {}, {}, {} has a negative count or start", count, start, base));
+                    continue;
+                }
+                let ib = match current_ib {
+                    Some(ib) => ib,
+                    None => continue,
+                };
+
+                let index_count = ib.info.element_count as i64;
+                let Some(end) = start.checked_add(count) else {
+                    issue(entry.line, LintSeverity::Error, "drawindexed-out-of-range", format!("drawindexed = {}, {}, {} overflows", count, start, base));
+                    continue;
+                };
+                if end > index_count {
+                    issue(
+                        entry.line,
+                        LintSeverity::Error,
+                        "drawindexed-out-of-range",
+                        format!("drawindexed = {}, {}, {} reads past the {} indices in [{}]", count, start, base, index_count, ib.info.resource),
+                    );
+                    continue;
+                }
+
+                let (Some(indices), Some(vertices)) = (&ib.indices, section_vb0.or(shared_vertex_count)) else {
+                    continue;
+                };
+                let range = &indices[start as usize..end as usize];
+                // base_vertex may be negative, but not below the first vertex
+                if let Some(min) = range.iter().min().filter(|&&min| (min as i64).saturating_add(base) < 0) {
+                    issue(
+                        entry.line,
+                        LintSeverity::Error,
+                        "index-out-of-range",
+                        format!("[{}] index {} with base vertex {} is below the first vertex", ib.info.resource, min, base),
+                    );
+                    continue;
+                }
+                if let Some(max) = range.iter().max() {
+                    let Some(highest) = (*max as i64).checked_add(base) else {
+                        issue(entry.line, LintSeverity::Error, "index-out-of-range", format!("drawindexed base vertex {} overflows", base));
+                        continue;
+                    };
+                    if highest >= vertices as i64 {
+                        issue(
+                            entry.line,
+                            LintSeverity::Error,
+                            "index-out-of-range",
+                            format!(
+                                "[{}] uses vertex {} but the vertex buffer only has {} vertices",
+                                ib.info.resource, highest, vertices
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    if shared_vertex_count.is_none() && vertex_counts.len() > 1 {
+        let line = loaded.values().map(|b| b.line).min().unwrap_or(0);
+        issue(line, LintSeverity::Info, "mixed-vertex-counts", "Vertex buffers have different vertex counts, index ranges were only checked where vb0 is bound in the same section".to_string());
+    }
+
+    let mut infos: Vec<BufferInfo> = loaded.into_values().map(|b| b.info).collect();
+    infos.sort_by(|a, b| a.resource.cmp(&b.resource));
+    buffers.extend(infos);
+}
+
+fn build_report(mod_relative_path: &str, files: &[ModIniFile]) -> ModBufferReport {
+    let mut buffers = Vec::new();
+    let mut issues = Vec::new();
+    for f in files {
+        validate_ini(&f.ini_path, &mut buffers, &mut issues);
+    }
+    issues.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    ModBufferReport {
+        mod_relative_path: mod_relative_path.to_string(),
+        buffers,
+        issues,
+    }
+}
+
+#[tauri::command]
+pub fn validate_mod_buffers(app: AppHandle, game_name: String, mod_relative_path: String) -> Result<ModBufferReport, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    let wanted = mod_relative_path.replace('\\', "/");
+    let files: Vec<ModIniFile> = collect_mod_inis(&mods_dir, get_mod_scan_depth(&app, &game_name))
+        .into_iter()
+        .filter(|f| f.mod_relative_path.replace('\\', "/").trim_matches('/') == wanted.trim_matches('/'))
+        .collect();
+
+    if files.is_empty() {
+        return Err("No .ini files found for this mod".to_string());
+    }
+    Ok(build_report(&mod_relative_path, &files))
+}
+
+// One report per enabled mod, mods without buffers are left out
+#[tauri::command]
+pub fn validate_all_mod_buffers(app: AppHandle, game_name: String) -> Result<Vec<ModBufferReport>, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    let mut files = collect_mod_inis(&mods_dir, get_mod_scan_depth(&app, &game_name));
+    files.retain(|f| f.enabled);
+    files.sort_by(|a, b| a.mod_relative_path.cmp(&b.mod_relative_path));
+
+    let reports = files
+        .chunk_by(|a, b| a.mod_relative_path == b.mod_relative_path)
+        .map(|chunk| build_report(&chunk[0].mod_relative_path, chunk))
+        .filter(|r| !r.buffers.is_empty() || !r.issues.is_empty())
+        .collect();
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::TempDir;
+
+    fn validate(dir: &TempDir, draws: &str) -> Vec<LintIssue> {
+        dir.write("body.vb", &"\0".repeat(12 * 4));
+        fs::write(dir.path().join("body.ib"), [0u8, 0, 5, 0]).unwrap();
+        let ini = dir.write(
+            "mod.ini",
+            &format!(
+                "[TextureOverrideBody]\nhash = 1\nvb0 = ResourceVB\nib = ResourceIB\n{}\n\
+                 [ResourceVB]\ntype = Buffer\nstride = 12\nfilename = body.vb\n\
+                 [ResourceIB]\ntype = Buffer\nformat = DXGI_FORMAT_R16_UINT\nfilename = body.ib\n",
+                draws
+            ),
+        );
+        let (mut buffers, mut issues) = (Vec::new(), Vec::new());
+        validate_ini(&ini, &mut buffers, &mut issues);
+        issues
+    }
+
+    fn codes(issues: &[LintIssue]) -> Vec<&str> {
+        issues.iter().map(|i| i.code.as_str()).collect()
+    }
+
+    #[test]
+    fn checks_draw_ranges() {
+        let dir = TempDir::new("mod_buffers_ranges");
+        assert!(validate(&dir, "draw = 4, 0\ndrawindexed = 1, 0, 0").is_empty());
+        assert_eq!(codes(&validate(&dir, "draw = 4, 1")), vec!["draw-out-of-range"]);
+        assert_eq!(codes(&validate(&dir, "drawindexed = 2, 1, 0")), vec!["drawindexed-out-of-range"]);
+        assert_eq!(codes(&validate(&dir, "drawindexed = 2, 0, 0")), vec!["index-out-of-range"]);
+    }
+
+    #[test]
+    fn negative_draw_arguments_are_errors() {
+        let dir = TempDir::new("mod_buffers_negative");
+        assert_eq!(codes(&validate(&dir, "draw = -1, 0")), vec!["draw-out-of-range"]);
+        assert_eq!(codes(&validate(&dir, "draw = 2, -1")), vec!["draw-out-of-range"]);
+        assert_eq!(codes(&validate(&dir, "drawindexed = -1, 0, 0")), vec!["drawindexed-out-of-range"]);
+        assert_eq!(codes(&validate(&dir, "drawindexed = 1, -1, 0")), vec!["drawindexed-out-of-range"]);
+        assert_eq!(codes(&validate(&dir, "drawindexed = 1, 0, -1")), vec!["index-out-of-range"]);
+    }
+
+    #[test]
+    fn buffers_are_classified_by_their_binding() {
+        let dir = TempDir::new("mod_buffers_kinds");
+        dir.write("Position.buf", &"\0".repeat(12 * 4));
+        dir.write("Blend.buf", &"\0".repeat(32 * 4));
+        dir.write("ShapeKeys.buf", &"\0".repeat(4 * 7));
+        fs::write(dir.path().join("Body.ib"), [0u8, 0, 3, 0]).unwrap();
+        let ini = dir.write(
+            "mod.ini",
+            "[TextureOverridePosition]\nhash = 1\nvb0 = ResourcePosition\n\
+             [TextureOverrideBody]\nhash = 2\nib = ResourceBody\nvb1 = ResourceBlend\ncs-t0 = ResourceShapeKeys\ndrawindexed = 2, 0, 0\n\
+             [ResourcePosition]\ntype = Buffer\nstride = 12\nfilename = Position.buf\n\
+             [ResourceBlend]\ntype = Buffer\nstride = 32\nfilename = Blend.buf\n\
+             [ResourceShapeKeys]\ntype = Buffer\nstride = 4\nfilename = ShapeKeys.buf\n\
+             [ResourceBody]\ntype = Buffer\nformat = DXGI_FORMAT_R16_UINT\nfilename = Body.ib\n",
+        );
+        let (mut buffers, mut issues) = (Vec::new(), Vec::new());
+        validate_ini(&ini, &mut buffers, &mut issues);
+        let kinds: Vec<(&str, &BufferKind)> = buffers.iter().map(|b| (b.resource.as_str(), &b.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("ResourceBlend", &BufferKind::Vertex),
+                ("ResourceBody", &BufferKind::Index),
+                ("ResourcePosition", &BufferKind::Vertex),
+                ("ResourceShapeKeys", &BufferKind::Other),
+            ]
+        );
+        // Shape keys have a different element count, but are not vertex buffers
+        assert!(issues.is_empty(), "{:?}", codes(&issues));
+    }
+
+    #[test]
+    fn overflowing_draws_are_errors() {
+        let dir = TempDir::new("mod_buffers_overflow");
+        let max = i64::MAX;
+        assert_eq!(codes(&validate(&dir, &format!("draw = {}, 1", max))), vec!["draw-out-of-range"]);
+        assert_eq!(codes(&validate(&dir, &format!("drawindexed = {}, 1, 0", max))), vec!["drawindexed-out-of-range"]);
+        assert_eq!(codes(&validate(&dir, &format!("drawindexed = 2, 0, {}", max))), vec!["index-out-of-range"]);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth};
+use crate::utils::mod_ini::{scan_mod_inis, ModIni, TruncatedScan};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+// Builds a namespace graph over every ini under Mods.
+// An ini provides its effective namespace (explicit `namespace =` or its path) and depends on
+// every other namespace it references through CommandList\ns\X, Resource\ns\X,
+// CustomShader\ns\X or $\ns\var.
+// Namespaces declared by the inis 3DMigoto loads outside Mods (d3dx.ini, its `include =` files
+// and ShaderFixes) always count as available.
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum DependencyStatus {
+    Missing,  // No installed mod declares the namespace
+    Disabled, // Declared only by disabled mods
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDependencyNode {
+    pub mod_relative_path: String,
+    pub enabled: bool,
+    pub namespaces: Vec<String>, // Namespaces provided by this mod's ini files
+    pub depends_on: Vec<String>, // Namespaces referenced outside its own
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDependencyEdge {
+    pub from: String, // Mod relative path
+    pub to: String,   // Mod relative path of the provider
+    pub namespace: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UnresolvedDependency {
+    pub mod_relative_path: String,
+    pub ini_path: String,
+    pub line: usize, // First line referencing the namespace
+    pub namespace: String,
+    pub status: DependencyStatus,
+    pub providers: Vec<String>, // Disabled mods that would satisfy it
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateNamespace {
+    pub namespace: String,
+    pub ini_paths: Vec<String>,
+    pub mods: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDependencyReport {
+    pub nodes: Vec<ModDependencyNode>,
+    pub edges: Vec<ModDependencyEdge>,
+    pub unresolved: Vec<UnresolvedDependency>,
+    pub duplicates: Vec<DuplicateNamespace>,
+    pub truncated: Vec<TruncatedScan>, // Folders below the scan depth, their inis were not analyzed
+}
+
+struct IniNode {
+    mod_relative_path: String,
+    ini_path: String,
+    enabled: bool,
+    namespace: String,
+    explicit: bool,
+    references: BTreeMap<String, usize>, // namespace -> first line
+}
+
+// d3dx.ini, the files it includes one by one, and ShaderFixes/*.ini
+fn global_inis(install_dir: &Path) -> Vec<PathBuf> {
+    let d3dx = install_dir.join("d3dx.ini");
+    let mut paths = vec![d3dx.clone()];
+    if let Ok(ini) = ModIni::load(&d3dx) {
+        for section in ini.sections.iter().filter(|s| s.name.eq_ignore_ascii_case("Include")) {
+            for entry in section.entries.iter().filter(|e| e.key.eq_ignore_ascii_case("include")) {
+                let path = install_dir.join(entry.value.replace('\\', "/"));
+                if !path.starts_with(install_dir.join("Mods")) {
+                    paths.push(path);
+                }
+            }
+        }
+    }
+    if let Ok(entries) = fs::read_dir(install_dir.join("ShaderFixes")) {
+        let mut shader_fixes: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("ini")).unwrap_or(false))
+            .collect();
+        shader_fixes.sort();
+        paths.extend(shader_fixes);
+    }
+    paths
+}
+
+fn global_namespaces(install_dir: &Path) -> BTreeSet<String> {
+    global_inis(install_dir)
+        .iter()
+        .filter(|p| p.is_file())
+        .filter_map(|p| ModIni::load(p).ok())
+        .map(|ini| ini.effective_namespace(install_dir))
+        .collect()
+}
+
+pub(crate) fn build_dependency_report(install_dir: &Path, scan_depth: usize) -> ModDependencyReport {
+    let mods_dir = install_dir.join("Mods");
+    let scan = scan_mod_inis(&mods_dir, scan_depth);
+    let global = global_namespaces(install_dir);
+
+    let mut inis = Vec::new();
+    for file in scan.files {
+        let ini = match ModIni::load(&file.ini_path) {
+            Ok(ini) => ini,
+            Err(e) => {
+                println!("[ModDependencies] {}", e);
+                continue;
+            }
+        };
+        let namespace = ini.effective_namespace(install_dir);
+        let mut references = BTreeMap::new();
+        for r in ini.namespace_references() {
+            if r.namespace != namespace {
+                references.entry(r.namespace).or_insert(r.line);
+            }
+        }
+        inis.push(IniNode {
+            mod_relative_path: file.mod_relative_path,
+            ini_path: file.ini_path.to_string_lossy().to_string(),
+            enabled: file.enabled,
+            namespace,
+            explicit: ini.namespace.is_some(),
+            references,
+        });
+    }
+
+    // namespace -> indices of the inis declaring it
+    let mut providers: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, node) in inis.iter().enumerate() {
+        providers.entry(node.namespace.as_str()).or_default().push(i);
+    }
+
+    let mut edges = BTreeSet::new();
+    let mut unresolved = Vec::new();
+    for node in inis.iter().filter(|n| n.enabled) {
+        for (namespace, line) in &node.references {
+            if global.contains(namespace) {
+                continue;
+            }
+            let candidates = providers.get(namespace.as_str()).cloned().unwrap_or_default();
+            let enabled: Vec<&IniNode> = candidates.iter().map(|&i| &inis[i]).filter(|n| n.enabled).collect();
+
+            if !enabled.is_empty() {
+                for provider in enabled {
+                    if provider.mod_relative_path != node.mod_relative_path {
+                        edges.insert((node.mod_relative_path.clone(), provider.mod_relative_path.clone(), namespace.clone()));
+                    }
+                }
+                continue;
+            }
+
+            let status = if candidates.is_empty() { DependencyStatus::Missing } else { DependencyStatus::Disabled };
+            let mut disabled_providers: Vec<String> = candidates.iter().map(|&i| inis[i].mod_relative_path.clone()).collect();
+            disabled_providers.dedup();
+            unresolved.push(UnresolvedDependency {
+                mod_relative_path: node.mod_relative_path.clone(),
+                ini_path: node.ini_path.clone(),
+                line: *line,
+                namespace: namespace.clone(),
+                status,
+                providers: disabled_providers,
+            });
+        }
+    }
+
+    // Only explicit namespaces can clash, path based ones are unique by definition
+    let mut duplicates = Vec::new();
+    for (namespace, indices) in &providers {
+        let declaring: Vec<&IniNode> = indices.iter().map(|&i| &inis[i]).filter(|n| n.enabled && n.explicit).collect();
+        if declaring.len() > 1 {
+            let mut mods: Vec<String> = declaring.iter().map(|n| n.mod_relative_path.clone()).collect();
+            mods.dedup();
+            duplicates.push(DuplicateNamespace {
+                namespace: namespace.to_string(),
+                ini_paths: declaring.iter().map(|n| n.ini_path.clone()).collect(),
+                mods,
+            });
+        }
+    }
+
+    // Collapse ini nodes into mod nodes
+    let mut nodes: BTreeMap<String, ModDependencyNode> = BTreeMap::new();
+    for ini in &inis {
+        let node = nodes.entry(ini.mod_relative_path.clone()).or_insert_with(|| ModDependencyNode {
+            mod_relative_path: ini.mod_relative_path.clone(),
+            enabled: false,
+            namespaces: Vec::new(),
+            depends_on: Vec::new(),
+        });
+        node.enabled |= ini.enabled;
+        node.namespaces.push(ini.namespace.clone());
+        node.depends_on.extend(ini.references.keys().cloned());
+    }
+    for node in nodes.values_mut() {
+        let own = node.namespaces.clone();
+        node.depends_on.sort();
+        node.depends_on.dedup();
+        node.depends_on.retain(|ns| !own.contains(ns));
+    }
+
+    ModDependencyReport {
+        nodes: nodes.into_values().collect(),
+        edges: edges
+            .into_iter()
+            .map(|(from, to, namespace)| ModDependencyEdge { from, to, namespace })
+            .collect(),
+        unresolved,
+        truncated: scan.truncated,
+        duplicates,
+    }
+}
+
+#[tauri::command]
+pub fn analyze_mod_dependencies(app: AppHandle, game_name: String) -> Result<ModDependencyReport, String> {
+    let install_dir = get_game_install_dir(&app, &game_name)?;
+    if !install_dir.join("Mods").exists() {
+        return Err("Mods directory not found".to_string());
+    }
+    Ok(build_dependency_report(&install_dir, get_mod_scan_depth(&app, &game_name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::TempDir;
+
+    const USER: &str = "[CommandListDraw]\nrun = CommandList\\global\\ORFix\\ORFix\n";
+
+    #[test]
+    fn namespaces_outside_mods_are_available() {
+        let dir = TempDir::new("deps-global");
+        dir.write("d3dx.ini", "[Include]\ninclude_recursive = Mods\ninclude = Extras\\helper.ini\n");
+        dir.write("ShaderFixes/orfix.ini", "namespace = global\\ORFix\n[CommandListORFix]\n");
+        dir.write("Extras/helper.ini", "namespace = global\\Helper\n[CommandListHelp]\n");
+        dir.write("Mods/Default/A/a.ini", &format!("{}run = CommandList\\global\\Helper\\Help\n", USER));
+
+        let report = build_dependency_report(dir.path(), 4);
+        assert!(report.unresolved.is_empty(), "{:?}", report.unresolved.iter().map(|u| &u.namespace).collect::<Vec<_>>());
+        assert!(report.edges.is_empty());
+    }
+
+    #[test]
+    fn namespaces_declared_nowhere_are_missing() {
+        let dir = TempDir::new("deps-missing");
+        dir.write("d3dx.ini", "[Include]\ninclude_recursive = Mods\n");
+        dir.write("Mods/Default/A/a.ini", USER);
+
+        let report = build_dependency_report(dir.path(), 4);
+        assert_eq!(report.unresolved.len(), 1);
+        assert_eq!(report.unresolved[0].status, DependencyStatus::Missing);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth, scan_folder, ScanState};
+use crate::utils::dds::read_dds_info;
+use crate::utils::file_manager::get_dir_size;
+use crate::utils::mod_ini::{scan_mod_inis, ModIni, ModIniFile, TruncatedScan};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+// Rough VRAM estimate per mod: every [Resource*] file the mod's inis load.
+// DDS textures use their header (all mips / layers), PNG/JPG are loaded as
+// RGBA8 without mips, buffers take their file size.
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModFootprint {
+    pub mod_relative_path: String,
+    pub name: String,
+    pub group: String,
+    pub enabled: bool,
+    pub disk_bytes: u64,
+    pub vram_bytes: u64,    // texture_bytes + buffer_bytes
+    pub texture_bytes: u64,
+    pub buffer_bytes: u64,
+    pub texture_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupFootprint {
+    pub group: String, // Logical group id, "Root" for mods directly under Mods (not the whole tree)
+    pub mod_count: usize,
+    pub enabled_count: usize,
+    pub disk_bytes: u64,
+    pub vram_bytes: u64,
+    pub enabled_vram_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModFootprintReport {
+    pub mods: Vec<ModFootprint>, // Heaviest first
+    pub groups: Vec<GroupFootprint>,
+    pub total_disk_bytes: u64,
+    pub enabled_disk_bytes: u64,
+    pub enabled_vram_bytes: u64,
+    pub truncated: Vec<TruncatedScan>, // Folders below the scan depth, not counted
+}
+
+#[derive(Default)]
+struct ResourceCost {
+    texture_bytes: u64,
+    buffer_bytes: u64,
+    texture_count: usize,
+}
+
+fn estimate_resources(files: &[&ModIniFile]) -> ResourceCost {
+    let mut cost = ResourceCost::default();
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+
+    for file in files {
+        let ini = match ModIni::load(&file.ini_path) {
+            Ok(ini) => ini,
+            Err(_) => continue,
+        };
+        let ini_dir = file.ini_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+
+        for section in ini.sections_with_prefix("Resource") {
+            let filename = match section.get("filename") {
+                Some(f) => f.trim_matches('"').replace('\\', "/"),
+                None => continue,
+            };
+            let path = ini_dir.join(&filename);
+            // The same file referenced twice is only uploaded once
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            let size = match fs::metadata(&path) {
+                Ok(m) => m.len(),
+                Err(_) => continue,
+            };
+
+            let extension = filename.rsplit('.').next().unwrap_or("").to_lowercase();
+            match extension.as_str() {
+                "dds" => {
+                    cost.texture_count += 1;
+                    let bytes = read_dds_info(&path).ok().and_then(|d| d.gpu_size_bytes().ok()).unwrap_or(size);
+                    cost.texture_bytes = cost.texture_bytes.saturating_add(bytes);
+                }
+                "png" | "jpg" | "jpeg" => {
+                    cost.texture_count += 1;
+                    let bytes = image::image_dimensions(&path)
+                        .map(|(w, h)| (w as u64).saturating_mul(h as u64).saturating_mul(4))
+                        .unwrap_or(size);
+                    cost.texture_bytes = cost.texture_bytes.saturating_add(bytes);
+                }
+                _ => cost.buffer_bytes = cost.buffer_bytes.saturating_add(size),
+            }
+        }
+    }
+    cost
+}
+
+pub(crate) fn build_footprint_report(mods_dir: &Path, scan_depth: usize) -> ModFootprintReport {
+    let mut state = ScanState::new(scan_depth);
+    scan_folder(mods_dir, mods_dir, "Root".to_string(), 0, false, &mut state);
+    let mods = state.mods;
+
+    // Same depth limit as the folder scan, so it reports the same truncated folders
+    let ini_files = scan_mod_inis(mods_dir, scan_depth).files;
+    let normalize = |p: &str| p.replace('\\', "/").trim_matches('/').to_string();
+
+    let mut footprints: Vec<ModFootprint> = mods
+        .iter()
+        .map(|m| {
+            let key = normalize(&m.relative_path);
+            let files: Vec<&ModIniFile> = ini_files.iter().filter(|f| normalize(&f.mod_relative_path) == key).collect();
+            let cost = estimate_resources(&files);
+            ModFootprint {
+                mod_relative_path: m.relative_path.clone(),
+                name: m.name.clone(),
+                group: m.group.clone(),
+                enabled: m.enabled,
+                disk_bytes: get_dir_size(Path::new(&m.path)),
+                vram_bytes: cost.texture_bytes.saturating_add(cost.buffer_bytes),
+                texture_bytes: cost.texture_bytes,
+                buffer_bytes: cost.buffer_bytes,
+                texture_count: cost.texture_count,
+            }
+        })
+        .collect();
+    footprints.sort_by(|a, b| b.vram_bytes.cmp(&a.vram_bytes).then(b.disk_bytes.cmp(&a.disk_bytes)));
+
+    // Groups include the mods of their subgroups ("A" covers "A/B"), the report totals cover everything
+    let mut groups: BTreeMap<String, GroupFootprint> = BTreeMap::new();
+    for m in &footprints {
+        let ids: Vec<String> = if m.group == "Root" {
+            vec!["Root".to_string()]
+        } else {
+            let parts: Vec<&str> = m.group.split('/').collect();
+            (1..=parts.len()).map(|i| parts[..i].join("/")).collect()
+        };
+        for id in ids {
+            let g = groups.entry(id.clone()).or_insert_with(|| GroupFootprint {
+                group: id,
+                mod_count: 0,
+                enabled_count: 0,
+                disk_bytes: 0,
+                vram_bytes: 0,
+                enabled_vram_bytes: 0,
+            });
+            g.mod_count += 1;
+            g.disk_bytes += m.disk_bytes;
+            g.vram_bytes += m.vram_bytes;
+            if m.enabled {
+                g.enabled_count += 1;
+                g.enabled_vram_bytes += m.vram_bytes;
+            }
+        }
+    }
+
+    let enabled = footprints.iter().filter(|m| m.enabled);
+    ModFootprintReport {
+        total_disk_bytes: footprints.iter().map(|m| m.disk_bytes).sum(),
+        enabled_disk_bytes: enabled.clone().map(|m| m.disk_bytes).sum(),
+        enabled_vram_bytes: enabled.map(|m| m.vram_bytes).sum(),
+        groups: groups.into_values().collect(),
+        mods: footprints,
+        truncated: state.truncated,
+    }
+}
+
+#[tauri::command]
+pub async fn get_mod_footprint(app: AppHandle, game_name: String) -> Result<ModFootprintReport, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    if !mods_dir.exists() {
+        return Err("Mods directory not found".to_string());
+    }
+    let scan_depth = get_mod_scan_depth(&app, &game_name);
+
+    tauri::async_runtime::spawn_blocking(move || build_footprint_report(&mods_dir, scan_depth))
+        .await
+        .map_err(|e| format!("Footprint task failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::TempDir;
+
+    #[test]
+    fn root_group_only_counts_ungrouped_mods() {
+        let dir = TempDir::new("mod_footprint_groups");
+        dir.write("Loose/loose.ini", "[TextureOverrideA]\nhash = 1\n");
+        dir.write("Characters/Ayaka/A/a.ini", "[TextureOverrideA]\nhash = 1\n");
+        dir.write("Characters/Ayaka/B/b.ini", "[TextureOverrideB]\nhash = 2\n");
+
+        let report = build_footprint_report(dir.path(), 4);
+        let counts: Vec<(&str, usize)> = report.groups.iter().map(|g| (g.group.as_str(), g.mod_count)).collect();
+        assert_eq!(counts, vec![("Characters", 2), ("Characters/Ayaka", 2), ("Root", 1)]);
+        assert_eq!(report.mods.len(), 3);
+    }
+}
@@ -0,0 +1,241 @@
+use crate::commands::mod_load_order::{order_prefix, record_priority, split_disabled_prefix, split_order_prefix};
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth, scan_folder, ModInfo, ScanState, TruncatedScan};
+use crate::commands::mod_transfer::{copy_or_move, next_free_name, remove_existing, ClashPolicy, TransferMode, TransferStatus};
+use crate::utils::mod_metadata::{load_mod_metadata, save_mod_metadata};
+use crate::utils::mod_paths::{check_file_name, resolve_mod_path};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+// Brings an existing mod collection into a game's Mods folder.
+// Plain: any folder tree, folders with an .ini are mods, the rest are groups.
+// Jasm: JASM layout (Category/Character/Mod), plus .JASM_ModConfig.json for the
+//       display name, cover image and download url.
+// Both use the DISABLED_ prefix for disabled mods, which is kept on the mod folder.
+
+const JASM_CONFIG_FILE: &str = ".JASM_ModConfig.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ImportLayout {
+    Auto,
+    Plain,
+    Jasm,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportItem {
+    pub source: String,         // Absolute path in the source library
+    pub target: Option<String>, // Relative path under Mods
+    pub name: String,
+    pub group: String,
+    pub enabled: bool,
+    pub preview_images: Vec<String>,
+    pub status: TransferStatus, // For a dry run: what would happen
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub layout: ImportLayout, // Detected layout when Auto was requested
+    pub dry_run: bool,
+    pub items: Vec<ImportItem>,
+    pub truncated: Vec<TruncatedScan>,
+}
+
+#[derive(Default)]
+struct JasmConfig {
+    custom_name: Option<String>,
+    image_path: Option<PathBuf>,
+    mod_url: Option<String>,
+}
+
+fn read_jasm_config(mod_dir: &Path) -> JasmConfig {
+    let value: serde_json::Value = match fs::read_to_string(mod_dir.join(JASM_CONFIG_FILE))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+    {
+        Some(v) => v,
+        None => return JasmConfig::default(),
+    };
+    let text = |key: &str| {
+        value.get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    // Image paths are stored as absolute paths or file:/// uris
+    let image_path = text("ImagePath").map(|p| {
+        let p = p.trim_start_matches("file:///").to_string();
+        let path = PathBuf::from(&p);
+        if path.is_absolute() { path } else { mod_dir.join(path) }
+    });
+
+    JasmConfig {
+        custom_name: text("CustomName"),
+        image_path,
+        mod_url: text("ModUrl"),
+    }
+}
+
+fn detect_layout(mods: &[ModInfo]) -> ImportLayout {
+    if mods.iter().any(|m| Path::new(&m.path).join(JASM_CONFIG_FILE).is_file()) {
+        ImportLayout::Jasm
+    } else {
+        ImportLayout::Plain
+    }
+}
+
+fn plan_target(m: &ModInfo, jasm: &JasmConfig) -> (String, String) {
+    let name = jasm
+        .custom_name
+        .as_deref()
+        .and_then(|n| check_file_name(n).ok())
+        .unwrap_or(&m.name)
+        .to_string();
+    // Same rule as install_mod_archive: nothing directly under Mods
+    let group = if m.group == "Root" { "Default".to_string() } else { m.group.clone() };
+    (name, group)
+}
+
+struct ImportOptions {
+    layout: ImportLayout,
+    mode: TransferMode,
+    on_clash: ClashPolicy,
+    dry_run: bool,
+}
+
+fn import_one(app: &AppHandle, game_name: &str, mods_dir: &Path, m: &ModInfo, options: &ImportOptions) -> Result<ImportItem, String> {
+    let ImportOptions { layout, mode, on_clash, dry_run } = *options;
+    let jasm = if layout == ImportLayout::Jasm { read_jasm_config(Path::new(&m.path)) } else { JasmConfig::default() };
+    let (name, group) = plan_target(m, &jasm);
+    // A "#050 " prefix from an ssmt4 Mods folder keeps its load order priority
+    let source_folder = Path::new(&m.path).file_name().unwrap_or_default().to_string_lossy().to_string();
+    let priority = split_order_prefix(split_disabled_prefix(&source_folder).1).0;
+    let ordered = match priority {
+        Some(p) => format!("{}{}", order_prefix(p), name),
+        None => name.clone(),
+    };
+    let folder = if m.enabled { ordered.clone() } else { format!("DISABLED_{}", ordered) };
+    let mut dest = resolve_mod_path(mods_dir, &format!("{}/{}", group, folder))?;
+
+    let mut item = ImportItem {
+        source: m.path.clone(),
+        target: None,
+        name: name.clone(),
+        group,
+        enabled: m.enabled,
+        preview_images: m.preview_images.clone(),
+        status: TransferStatus::Transferred,
+        error: None,
+    };
+
+    // The same mod in the opposite enabled state also counts as a clash
+    let counterpart = dest.with_file_name(if m.enabled { format!("DISABLED_{}", ordered) } else { ordered });
+    let existing = [dest.clone(), counterpart].into_iter().find(|p| fs::symlink_metadata(p).is_ok());
+    if let Some(existing) = existing {
+        match on_clash {
+            ClashPolicy::Skip => {
+                item.status = TransferStatus::Skipped;
+                item.target = Some(existing.strip_prefix(mods_dir).unwrap_or(&existing).to_string_lossy().to_string());
+                return Ok(item);
+            }
+            ClashPolicy::Rename => {
+                dest = next_free_name(&dest)?;
+                item.status = TransferStatus::Renamed;
+            }
+            ClashPolicy::Replace => {
+                if !dry_run {
+                    remove_existing(app, game_name, mods_dir, &existing)?;
+                }
+                item.status = TransferStatus::Replaced;
+            }
+        }
+    }
+    item.target = Some(dest.strip_prefix(mods_dir).unwrap_or(&dest).to_string_lossy().to_string());
+    if dry_run {
+        return Ok(item);
+    }
+
+    copy_or_move(Path::new(&m.path), &dest, mode)?;
+    if priority.is_some() {
+        record_priority(app, game_name, &dest.strip_prefix(mods_dir).unwrap_or(&dest).to_string_lossy(), priority)?;
+    }
+
+    // JASM covers can live outside the mod folder, copy them in so they show as previews
+    if let Some(image) = jasm.image_path.filter(|p| p.is_file() && !p.starts_with(&m.path)) {
+        let ext = image.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_else(|| "png".to_string());
+        let _ = fs::copy(&image, dest.join(format!("preview.{}", ext)));
+    }
+    if let Some(url) = jasm.mod_url {
+        let mut metadata = load_mod_metadata(&dest);
+        metadata.source_url = Some(url);
+        save_mod_metadata(&dest, &metadata)?;
+    }
+    Ok(item)
+}
+
+// dry_run = true only reports what would be imported and where
+#[tauri::command]
+pub async fn import_mods(
+    app: AppHandle,
+    game_name: String,
+    source_dir: String,
+    layout: Option<ImportLayout>,
+    mode: Option<TransferMode>,
+    on_clash: Option<ClashPolicy>,
+    dry_run: bool,
+) -> Result<ImportReport, String> {
+    let source = PathBuf::from(&source_dir);
+    if !source.is_dir() {
+        return Err(format!("Source folder not found: {}", source_dir));
+    }
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    fs::create_dir_all(&mods_dir).map_err(|e| format!("Failed to create Mods directory: {}", e))?;
+
+    let source_canonical = source.canonicalize().map_err(|e| format!("Failed to read source folder: {}", e))?;
+    let mods_canonical = mods_dir.canonicalize().map_err(|e| format!("Failed to read Mods folder: {}", e))?;
+    if source_canonical.starts_with(&mods_canonical) || mods_canonical.starts_with(&source_canonical) {
+        return Err("Source folder overlaps with this game's Mods folder".to_string());
+    }
+
+    let mut state = ScanState::new(get_mod_scan_depth(&app, &game_name));
+    scan_folder(&source, &source, "Root".to_string(), 0, false, &mut state);
+
+    let layout = match layout.unwrap_or(ImportLayout::Auto) {
+        ImportLayout::Auto => detect_layout(&state.mods),
+        other => other,
+    };
+    let options = ImportOptions {
+        layout,
+        mode: mode.unwrap_or(TransferMode::Copy),
+        on_clash: on_clash.unwrap_or(ClashPolicy::Rename),
+        dry_run,
+    };
+    println!("[ModImporter] {} mods from {:?} ({:?}, dry run: {})", state.mods.len(), source, layout, dry_run);
+
+    let mut items = Vec::new();
+    for m in &state.mods {
+        let item = import_one(&app, &game_name, &mods_dir, m, &options).unwrap_or_else(|e| ImportItem {
+            source: m.path.clone(),
+            target: None,
+            name: m.name.clone(),
+            group: m.group.clone(),
+            enabled: m.enabled,
+            preview_images: m.preview_images.clone(),
+            status: TransferStatus::Failed,
+            error: Some(e),
+        });
+        items.push(item);
+    }
+
+    Ok(ImportReport {
+        layout,
+        dry_run,
+        items,
+        truncated: state.truncated,
+    })
+}
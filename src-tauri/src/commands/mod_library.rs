@@ -0,0 +1,444 @@
+use crate::commands::game_config::load_game_config;
+use crate::commands::mod_manager::{find_preview_images, get_game_install_dir};
+use crate::configs::app_config::AppConfig;
+use crate::utils::file_manager::{copy_dir_recursive, get_global_games_dir};
+use crate::utils::mod_paths::{resolve_mod_path, resolve_under};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+// Mods in the library are never renamed with DISABLED_, they are "enabled" for a game
+// by linking them into <installDir>/Mods and "disabled" by removing that link again.
+// The same library folder can back several game configs (e.g. two presets of one game).
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum DeployMode {
+    Symlink,  // Directory symlink, needs Developer Mode or admin on Windows
+    Hardlink, // Directory tree recreated with hard linked files, needs the same volume
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryDeployment {
+    pub library_id: String, // Relative path inside the library, e.g. "Ayaka/ModA"
+    pub target: String,     // Relative path inside Mods where the link lives
+    pub mode: DeployMode,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct DeploymentManifest {
+    #[serde(default)]
+    deployments: Vec<LibraryDeployment>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryModInfo {
+    pub id: String,    // Relative path inside the library
+    pub name: String,
+    pub group: String, // "Root" or "Ayaka/Outfits"
+    pub path: String,  // Absolute path
+    pub preview_images: Vec<String>,
+    pub deployed: bool, // Linked into this game's Mods folder
+    pub deploy_mode: Option<DeployMode>,
+}
+
+fn normalize_relative(p: &str) -> String {
+    p.replace('\\', "/").trim_matches('/').to_string()
+}
+
+fn strip_disabled_prefix(name: &str) -> &str {
+    let upper = name.to_uppercase();
+    if upper.starts_with("DISABLED_") {
+        &name[9..]
+    } else if upper.starts_with("DISABLED") && name.len() > 8 {
+        &name[8..]
+    } else {
+        name
+    }
+}
+
+pub(crate) fn get_library_dir(app: &AppHandle, game_name: &str) -> Result<PathBuf, String> {
+    let game_config = load_game_config(app.clone(), game_name.to_string())?;
+    let library_name = if game_config.basic.mod_library.trim().is_empty() {
+        game_config.basic.game_preset
+    } else {
+        game_config.basic.mod_library
+    };
+
+    let app_config = AppConfig::load().map_err(|e| format!("Failed to load app config: {}", e))?;
+    if app_config.cache_dir.is_empty() {
+        return Err("Cache Dir not set, cannot locate the mod library.".into());
+    }
+
+    let library_dir = PathBuf::from(&app_config.cache_dir)
+        .join("ModLibrary")
+        .join(library_name.trim());
+    if !library_dir.exists() {
+        fs::create_dir_all(&library_dir)
+            .map_err(|e| format!("Failed to create mod library at {:?}: {}", library_dir, e))?;
+    }
+    Ok(library_dir)
+}
+
+fn get_manifest_path(app: &AppHandle, game_name: &str) -> PathBuf {
+    get_global_games_dir(app)
+        .join(game_name)
+        .join("LibraryDeployments.json")
+}
+
+fn load_manifest(app: &AppHandle, game_name: &str) -> DeploymentManifest {
+    let path = get_manifest_path(app, game_name);
+    fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_manifest(app: &AppHandle, game_name: &str, manifest: &DeploymentManifest) -> Result<(), String> {
+    let path = get_manifest_path(app, game_name);
+    let content = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize deployments: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write deployments: {}", e))
+}
+
+// All deployments of a game, for callers that look up many paths at once (scan_mods)
+pub(crate) fn load_deployments(app: &AppHandle, game_name: &str) -> Vec<LibraryDeployment> {
+    load_manifest(app, game_name).deployments
+}
+
+pub(crate) fn find_deployment_in<'a>(deployments: &'a [LibraryDeployment], mod_relative_path: &str) -> Option<&'a LibraryDeployment> {
+    let wanted = normalize_relative(mod_relative_path);
+    deployments
+        .iter()
+        .find(|d| normalize_relative(&d.target).eq_ignore_ascii_case(&wanted))
+}
+
+// Look up the deployment whose link lives at `mod_relative_path` inside Mods
+pub(crate) fn find_deployment(app: &AppHandle, game_name: &str, mod_relative_path: &str) -> Option<LibraryDeployment> {
+    find_deployment_in(&load_deployments(app, game_name), mod_relative_path).cloned()
+}
+
+// Point every deployment at or below `old_prefix` to `new_prefix`. Returns whether any changed.
+fn retarget_under(deployments: &mut [LibraryDeployment], old_prefix: &str, new_prefix: &str) -> bool {
+    let (old_prefix, new_prefix) = (normalize_relative(old_prefix), normalize_relative(new_prefix));
+    let mut changed = false;
+    for deployment in deployments.iter_mut() {
+        let target = normalize_relative(&deployment.target);
+        let rest = match target.get(..old_prefix.len()) {
+            Some(head) if head.eq_ignore_ascii_case(&old_prefix) => &target[old_prefix.len()..],
+            _ => continue,
+        };
+        if rest.is_empty() || rest.starts_with('/') {
+            deployment.target = format!("{}{}", new_prefix, rest);
+            changed = true;
+        }
+    }
+    changed
+}
+
+// Keep the manifest in sync after a deployed link, or a group folder holding links,
+// was renamed, moved or toggled inside Mods
+pub(crate) fn retarget_deployment(app: &AppHandle, game_name: &str, old_target: &str, new_target: &str) -> Result<(), String> {
+    let mut manifest = load_manifest(app, game_name);
+    if !retarget_under(&mut manifest.deployments, old_target, new_target) {
+        return Ok(());
+    }
+    save_manifest(app, game_name, &manifest)
+}
+
+#[cfg(windows)]
+fn symlink_dir(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(src, dst)
+}
+
+#[cfg(unix)]
+fn symlink_dir(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(src, dst)
+}
+
+// Recreate the directory structure and hard link every file into it
+fn hardlink_tree(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            hardlink_tree(&src_path, &dst_path)?;
+        } else {
+            fs::hard_link(&src_path, &dst_path)?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_link(target: &Path, mode: DeployMode) -> Result<(), String> {
+    let metadata = match fs::symlink_metadata(target) {
+        Ok(m) => m,
+        Err(_) => return Ok(()), // Already gone, nothing to clean up
+    };
+
+    match mode {
+        DeployMode::Symlink => {
+            if !metadata.file_type().is_symlink() {
+                return Err(format!("{:?} is no longer a link, refusing to remove it", target));
+            }
+            // Directory symlinks are removed with remove_dir on Windows, remove_file elsewhere
+            fs::remove_file(target)
+                .or_else(|_| fs::remove_dir(target))
+                .map_err(|e| format!("Failed to remove link: {}", e))
+        }
+        // Only hard links live in here, the library copy keeps the data
+        DeployMode::Hardlink => {
+            fs::remove_dir_all(target).map_err(|e| format!("Failed to remove linked copy: {}", e))
+        }
+    }
+}
+
+fn link_into_mods(src: &Path, target: &Path, mode: Option<DeployMode>) -> Result<DeployMode, String> {
+    match mode {
+        Some(DeployMode::Symlink) => symlink_dir(src, target)
+            .map(|_| DeployMode::Symlink)
+            .map_err(|e| format!("Failed to create symlink: {}", e)),
+        Some(DeployMode::Hardlink) => match hardlink_tree(src, target) {
+            Ok(_) => Ok(DeployMode::Hardlink),
+            Err(e) => {
+                let _ = fs::remove_dir_all(target);
+                Err(format!("Failed to create hard links (library and Mods must be on the same drive): {}", e))
+            }
+        },
+        None => {
+            // Prefer a symlink, fall back to hard links when symlinks are not permitted
+            if let Err(e) = symlink_dir(src, target) {
+                println!("[ModLibrary] Symlink failed ({}), falling back to hard links", e);
+                link_into_mods(src, target, Some(DeployMode::Hardlink))
+            } else {
+                Ok(DeployMode::Symlink)
+            }
+        }
+    }
+}
+
+fn has_ini_file(path: &Path) -> bool {
+    fs::read_dir(path)
+        .map(|r| {
+            r.flatten().any(|e| {
+                e.path()
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("ini"))
+                    .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn scan_library_folder(
+    library_dir: &Path,
+    current_dir: &Path,
+    current_group: String,
+    depth: usize,
+    deployments: &[LibraryDeployment],
+    results: &mut Vec<LibraryModInfo>,
+) {
+    if depth > 5 { return; }
+
+    if let Ok(entries) = fs::read_dir(current_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let id = normalize_relative(&path.strip_prefix(library_dir).unwrap_or(&path).to_string_lossy());
+
+            if has_ini_file(&path) {
+                let deployment = deployments.iter().find(|d| d.library_id == id);
+                results.push(LibraryModInfo {
+                    id,
+                    name,
+                    group: current_group.clone(),
+                    path: path.to_string_lossy().to_string(),
+                    preview_images: find_preview_images(&path),
+                    deployed: deployment.is_some(),
+                    deploy_mode: deployment.map(|d| d.mode),
+                });
+            } else {
+                let next_group = if current_group == "Root" {
+                    name.clone()
+                } else {
+                    format!("{}/{}", current_group, name)
+                };
+                scan_library_folder(library_dir, &path, next_group, depth + 1, deployments, results);
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub fn scan_mod_library(app: AppHandle, game_name: String) -> Result<Vec<LibraryModInfo>, String> {
+    let library_dir = get_library_dir(&app, &game_name)?;
+    let manifest = load_manifest(&app, &game_name);
+
+    let mut mods = Vec::new();
+    scan_library_folder(&library_dir, &library_dir, "Root".to_string(), 0, &manifest.deployments, &mut mods);
+    mods.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(mods)
+}
+
+#[tauri::command]
+pub fn deploy_library_mod(
+    app: AppHandle,
+    game_name: String,
+    library_id: String,
+    mode: Option<DeployMode>, // None = symlink with hard link fallback
+    target: Option<String>,   // Link location relative to Mods, None = same as the library id
+) -> Result<String, String> {
+    let library_id = normalize_relative(&library_id);
+    let target_relative = target
+        .map(|t| normalize_relative(&t))
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| library_id.clone());
+    let library_dir = get_library_dir(&app, &game_name)?;
+    let src = resolve_under(&library_dir, &library_id)?;
+    if !src.is_dir() {
+        return Err(format!("Library mod not found: {}", library_id));
+    }
+
+    let mut manifest = load_manifest(&app, &game_name);
+    if let Some(existing) = manifest.deployments.iter().find(|d| d.library_id == library_id) {
+        return Ok(existing.target.clone()); // Already enabled
+    }
+
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    fs::create_dir_all(&mods_dir).map_err(|e| format!("Failed to create Mods directory: {}", e))?;
+    let target = resolve_mod_path(&mods_dir, &target_relative)?;
+    if fs::symlink_metadata(&target).is_ok() {
+        return Err(format!("Mods already contains a folder at {}", target_relative));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create group folder: {}", e))?;
+    }
+
+    let used_mode = link_into_mods(&src, &target, mode)?;
+    println!("[ModLibrary] Deployed {} into {:?} as {:?}", library_id, target, used_mode);
+
+    manifest.deployments.push(LibraryDeployment {
+        library_id,
+        target: target_relative.clone(),
+        mode: used_mode,
+    });
+    save_manifest(&app, &game_name, &manifest)?;
+
+    Ok(target_relative)
+}
+
+#[tauri::command]
+pub fn undeploy_library_mod(app: AppHandle, game_name: String, library_id: String) -> Result<(), String> {
+    let library_id = normalize_relative(&library_id);
+    let mut manifest = load_manifest(&app, &game_name);
+    let index = manifest
+        .deployments
+        .iter()
+        .position(|d| d.library_id == library_id)
+        .ok_or("Library mod is not deployed for this game")?;
+
+    let deployment = manifest.deployments[index].clone();
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    remove_link(&mods_dir.join(&deployment.target), deployment.mode)?;
+
+    manifest.deployments.remove(index);
+    save_manifest(&app, &game_name, &manifest)?;
+    println!("[ModLibrary] Removed deployment of {}", library_id);
+    Ok(())
+}
+
+// Move a mod out of <installDir>/Mods into the library; enabled mods are linked back in place
+#[tauri::command]
+pub fn move_mod_to_library(app: AppHandle, game_name: String, mod_relative_path: String) -> Result<String, String> {
+    if find_deployment(&app, &game_name, &mod_relative_path).is_some() {
+        return Err("This mod already lives in the library".to_string());
+    }
+
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    let src = resolve_mod_path(&mods_dir, &mod_relative_path)?;
+    if !src.is_dir() {
+        return Err("Mod directory not found".to_string());
+    }
+
+    // Library ids carry no DISABLED_ prefixes, the enabled state is the deployment itself
+    let relative = normalize_relative(&mod_relative_path);
+    let was_enabled = !relative
+        .split('/')
+        .any(|part| part.to_uppercase().starts_with("DISABLED"));
+    let library_id = relative
+        .split('/')
+        .map(strip_disabled_prefix)
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let library_dir = get_library_dir(&app, &game_name)?;
+    let dest = resolve_under(&library_dir, &library_id)?;
+    if dest.exists() {
+        return Err(format!("The library already contains {}", library_id));
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create library group: {}", e))?;
+    }
+
+    // rename fails across drives, fall back to copy + delete
+    if fs::rename(&src, &dest).is_err() {
+        copy_dir_recursive(&src, &dest).map_err(|e| format!("Failed to copy mod into library: {}", e))?;
+        fs::remove_dir_all(&src).map_err(|e| format!("Copied into library but failed to remove original: {}", e))?;
+    }
+
+    if was_enabled {
+        deploy_library_mod(app, game_name, library_id.clone(), None, None)?;
+    }
+
+    Ok(library_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(target: &str) -> LibraryDeployment {
+        LibraryDeployment { library_id: target.to_string(), target: target.to_string(), mode: DeployMode::Symlink }
+    }
+
+    fn targets(deployments: &[LibraryDeployment]) -> Vec<&str> {
+        deployments.iter().map(|d| d.target.as_str()).collect()
+    }
+
+    #[test]
+    fn retargets_the_link_itself() {
+        let mut list = vec![deployment("Ayaka/ModA"), deployment("Ayaka/ModB")];
+        assert!(retarget_under(&mut list, r"Ayaka\ModA", "Ayaka/DISABLED_ModA"));
+        assert_eq!(targets(&list), vec!["Ayaka/DISABLED_ModA", "Ayaka/ModB"]);
+    }
+
+    #[test]
+    fn retargets_links_inside_a_group() {
+        let mut list = vec![deployment("Ayaka/ModA"), deployment("Ayaka/Outfits/ModB"), deployment("AyakaAlt/ModC")];
+        assert!(retarget_under(&mut list, "ayaka", "DISABLED_Ayaka"));
+        // AyakaAlt only shares the name prefix, not the folder
+        assert_eq!(targets(&list), vec!["DISABLED_Ayaka/ModA", "DISABLED_Ayaka/Outfits/ModB", "AyakaAlt/ModC"]);
+    }
+
+    #[test]
+    fn unrelated_paths_are_left_alone() {
+        let mut list = vec![deployment("Raiden/ModA")];
+        assert!(!retarget_under(&mut list, "Ayaka", "Default/Ayaka"));
+        assert_eq!(targets(&list), vec!["Raiden/ModA"]);
+    }
+
+    #[test]
+    fn finds_deployments_case_and_separator_insensitive() {
+        let list = vec![deployment("Ayaka/ModA")];
+        assert!(find_deployment_in(&list, r"ayaka\moda").is_some());
+        assert!(find_deployment_in(&list, "Ayaka").is_none());
+    }
+}
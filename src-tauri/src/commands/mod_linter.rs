@@ -0,0 +1,264 @@
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth};
+use crate::utils::mod_ini::{collect_mod_inis, ModIni, ModIniFile};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum LintSeverity {
+    Error,   // 3DMigoto will reject or misbehave on this
+    Warning, // Likely a mistake, but loads
+    Info,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LintIssue {
+    pub file: String,
+    pub line: usize,
+    pub severity: LintSeverity,
+    pub code: String, // Stable identifier, e.g. "missing-file"
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModLintReport {
+    pub mod_relative_path: String,
+    pub enabled: bool,
+    pub files_checked: usize,
+    pub error_count: usize,
+    pub warning_count: usize,
+    pub issues: Vec<LintIssue>,
+}
+
+// [Include] and [Include*] are covered by the prefix list
+const KNOWN_SECTIONS: [&str; 15] = [
+    "Constants", "Present", "Logging", "System", "Device", "Stereo", "Rendering", "Hunting",
+    "Loader", "Profile", "ConvergenceMap", "ClearRenderTargetView",
+    "ClearDepthStencilView", "ClearUnorderedAccessViewUint", "ClearUnorderedAccessViewFloat",
+];
+
+const KNOWN_SECTION_PREFIXES: [&str; 11] = [
+    "TextureOverride", "ShaderOverride", "ShaderRegex", "Resource", "CustomShader", "CommandList",
+    "Key", "Preset", "BuiltInCustomShader", "BuiltInCommandList", "Include",
+];
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.get(..prefix.len()).map(|h| h.eq_ignore_ascii_case(prefix)).unwrap_or(false)
+}
+
+fn is_known_section(name: &str) -> bool {
+    KNOWN_SECTIONS.iter().any(|s| s.eq_ignore_ascii_case(name))
+        || KNOWN_SECTION_PREFIXES.iter().any(|p| starts_with_ignore_case(name, p))
+}
+
+// Local (same file) section references: "ResourceFoo", "CommandListBar", "CustomShaderBaz"
+fn local_references(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | '(' | ')' | '=' | '!' | '<' | '>' | '&' | '|' | '+' | '*'))
+        // Namespaced refs are checked by mod_dependencies, file names are not sections
+        .filter(|t| !t.contains(['\\', '/', '.']))
+        .filter(|t| {
+            ["Resource", "CommandList", "CustomShader"]
+                .iter()
+                .any(|p| t.len() > p.len() && starts_with_ignore_case(t, p))
+        })
+}
+
+pub(crate) fn lint_ini_file(path: &Path) -> Vec<LintIssue> {
+    let file = path.to_string_lossy().to_string();
+    let mut issues = Vec::new();
+    let mut issue = |line: usize, severity: LintSeverity, code: &str, message: String| {
+        issues.push(LintIssue { file: file.clone(), line, severity, code: code.to_string(), message });
+    };
+
+    let content = match fs::read(path) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+        Err(e) => {
+            issue(0, LintSeverity::Error, "unreadable", format!("Failed to read file: {}", e));
+            return issues;
+        }
+    };
+
+    // Header lines the parser would still accept, but 3DMigoto will not
+    for (i, raw) in content.lines().enumerate() {
+        let trimmed = raw.trim();
+        if trimmed.starts_with('[') && !trimmed.contains(']') {
+            issue(i + 1, LintSeverity::Error, "unclosed-section", format!("Section header is missing ']': {}", trimmed));
+        }
+    }
+
+    let ini = ModIni::parse(path.to_path_buf(), &content);
+    let ini_dir = path.parent().unwrap_or(Path::new("."));
+
+    let declared: HashSet<String> = ini.sections.iter().map(|s| s.name.to_lowercase()).collect();
+    let mut seen = HashSet::new();
+    let mut used = HashSet::new();
+
+    for section in &ini.sections {
+        let lower_name = section.name.to_lowercase();
+        if !seen.insert(lower_name.clone()) {
+            issue(section.line, LintSeverity::Warning, "duplicate-section", format!("Section [{}] is declared more than once", section.name));
+        }
+        if !is_known_section(&section.name) {
+            issue(section.line, LintSeverity::Warning, "unknown-section", format!("Unknown section type [{}]", section.name));
+        }
+
+        // if/else/endif must balance inside each section
+        let mut depth: i32 = 0;
+        for entry in &section.entries {
+            match entry.key.as_str() {
+                "if" => depth += 1,
+                "endif" => {
+                    if depth == 0 {
+                        issue(entry.line, LintSeverity::Error, "unbalanced-if", "'endif' without matching 'if'".to_string());
+                    } else {
+                        depth -= 1;
+                    }
+                }
+                "else" | "elif" | "else if" if depth == 0 => {
+                    issue(entry.line, LintSeverity::Error, "unbalanced-if", format!("'{}' without matching 'if'", entry.key));
+                }
+                _ => {}
+            }
+        }
+        if depth > 0 {
+            issue(section.line, LintSeverity::Error, "unbalanced-if", format!("[{}] has {} 'if' block(s) without 'endif'", section.name, depth));
+        }
+
+        if starts_with_ignore_case(&section.name, "Resource") {
+            if let Some(filename) = section.get("filename") {
+                let filename = filename.trim_matches('"');
+                if !ini_dir.join(filename.replace('\\', "/")).exists() {
+                    issue(section.line, LintSeverity::Error, "missing-file", format!("[{}] filename={} does not exist", section.name, filename));
+                }
+            }
+        }
+
+        // Both sides count: "run = CommandListFoo" and "ResourceBar = copy ps-t0"
+        for entry in section.entries.iter().filter(|e| !e.key.eq_ignore_ascii_case("filename")) {
+            for reference in local_references(&entry.key).chain(local_references(&entry.value)) {
+                let reference_lower = reference.to_lowercase();
+                if reference_lower == lower_name {
+                    continue;
+                }
+                if declared.contains(&reference_lower) {
+                    used.insert(reference_lower);
+                } else {
+                    issue(entry.line, LintSeverity::Error, "undefined-reference", format!("[{}] references undefined section {}", section.name, reference));
+                }
+            }
+        }
+    }
+
+    for section in ini.sections_with_prefix("Resource") {
+        if !used.contains(&section.name.to_lowercase()) {
+            issue(section.line, LintSeverity::Info, "unused-resource", format!("[{}] is declared but never used", section.name));
+        }
+    }
+
+    issues.sort_by_key(|i| i.line);
+    issues
+}
+
+fn build_report(mod_relative_path: &str, files: &[ModIniFile]) -> ModLintReport {
+    let issues: Vec<LintIssue> = files.iter().flat_map(|f| lint_ini_file(&f.ini_path)).collect();
+    ModLintReport {
+        mod_relative_path: mod_relative_path.to_string(),
+        enabled: files.iter().any(|f| f.enabled),
+        files_checked: files.len(),
+        error_count: issues.iter().filter(|i| i.severity == LintSeverity::Error).count(),
+        warning_count: issues.iter().filter(|i| i.severity == LintSeverity::Warning).count(),
+        issues,
+    }
+}
+
+fn same_relative_path(a: &str, b: &str) -> bool {
+    a.replace('\\', "/").trim_matches('/') == b.replace('\\', "/").trim_matches('/')
+}
+
+#[tauri::command]
+pub fn lint_mod(app: AppHandle, game_name: String, mod_relative_path: String) -> Result<ModLintReport, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    let files: Vec<ModIniFile> = collect_mod_inis(&mods_dir, get_mod_scan_depth(&app, &game_name))
+        .into_iter()
+        .filter(|f| same_relative_path(&f.mod_relative_path, &mod_relative_path))
+        .collect();
+
+    if files.is_empty() {
+        return Err("No .ini files found for this mod".to_string());
+    }
+    Ok(build_report(&mod_relative_path, &files))
+}
+
+#[tauri::command]
+pub fn lint_all_mods(app: AppHandle, game_name: String, include_disabled: Option<bool>) -> Result<Vec<ModLintReport>, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    let include_disabled = include_disabled.unwrap_or(false);
+
+    let mut files = collect_mod_inis(&mods_dir, get_mod_scan_depth(&app, &game_name));
+    if !include_disabled {
+        files.retain(|f| f.enabled);
+    }
+    files.sort_by(|a, b| a.mod_relative_path.cmp(&b.mod_relative_path));
+
+    let mut reports = Vec::new();
+    for chunk in files.chunk_by(|a, b| a.mod_relative_path == b.mod_relative_path) {
+        reports.push(build_report(&chunk[0].mod_relative_path, chunk));
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::TempDir;
+
+    fn codes(issues: &[LintIssue]) -> Vec<&str> {
+        issues.iter().map(|i| i.code.as_str()).collect()
+    }
+
+    #[test]
+    fn assignments_to_resources_are_references() {
+        let dir = TempDir::new("mod_linter_keys");
+        let ini = dir.write(
+            "mod.ini",
+            "[TextureOverrideBody]\nhash = 1\nResourceSaved = ref ps-t0\npost ResourceBackup = copy ResourceSaved\n\
+             [ResourceSaved]\n[ResourceBackup]\n",
+        );
+        assert!(codes(&lint_ini_file(&ini)).is_empty());
+    }
+
+    #[test]
+    fn undefined_key_references_are_reported() {
+        let dir = TempDir::new("mod_linter_undefined");
+        let ini = dir.write("mod.ini", "[TextureOverrideBody]\nhash = 1\nResourceMissing = ref ps-t0\n");
+        assert_eq!(codes(&lint_ini_file(&ini)), vec!["undefined-reference"]);
+    }
+
+    #[test]
+    fn file_names_are_not_references() {
+        let dir = TempDir::new("mod_linter_filenames");
+        dir.write("Resources/ResourceBody.dds", "");
+        dir.write("ResourceFoo.buf", "");
+        let ini = dir.write(
+            "mod.ini",
+            "[TextureOverrideBody]\nhash = 1\nps-t0 = ResourceBody\nvb0 = ResourceFoo\n\
+             [ResourceBody]\nfilename = Resources/ResourceBody.dds\n[ResourceFoo]\nfilename = ResourceFoo.buf\n",
+        );
+        assert!(codes(&lint_ini_file(&ini)).is_empty());
+    }
+
+    #[test]
+    fn flags_unused_resources_and_unbalanced_ifs() {
+        let dir = TempDir::new("mod_linter_misc");
+        let ini = dir.write("mod.ini", "[ResourceUnused]\n[CommandListA]\nif $x == 1\nrun = CommandListA\n[IncludeFoo]\n[Bogus]\n");
+        let issues = lint_ini_file(&ini);
+        assert!(codes(&issues).contains(&"unused-resource"));
+        assert!(codes(&issues).contains(&"unbalanced-if"));
+        assert_eq!(issues.iter().filter(|i| i.code == "unknown-section").count(), 1);
+    }
+}
@@ -0,0 +1,473 @@
+use crate::commands::mod_library::retarget_deployment;
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth, ModInfo};
+use crate::utils::file_manager::get_global_games_dir;
+use crate::utils::mod_ini::{collect_mod_inis, ModIni};
+use crate::utils::mod_paths::resolve_mod_path;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+// 3DMigoto loads mod inis sorted case-insensitively by path, and when two mods override
+// the same hash the one loaded last draws over the other. Priorities are applied by
+// prefixing sibling mod folders with "#NNN " (after any DISABLED_ prefix), so a higher
+// priority sorts later and wins. Only siblings can be ordered this way: the group
+// folders above them decide the order across groups. Mods without a priority keep
+// their name, so they load after the prefixed ones ('#' sorts before letters and digits).
+//
+// Priorities are stored per game in ModPriorities.json rather than inside the mod, since
+// library deployments are recreated from the library copy and would drop them. Keys are
+// mod paths without DISABLED_ / order prefixes, lowercased, so toggling and reordering
+// do not change them.
+
+pub const MAX_PRIORITY: i32 = 999;
+const PRIORITIES_FILE: &str = "ModPriorities.json";
+
+// "#050 Name" -> (Some(50), "Name")
+pub(crate) fn split_order_prefix(name: &str) -> (Option<i32>, &str) {
+    let bytes = name.as_bytes();
+    if bytes.len() > 5 && bytes[0] == b'#' && bytes[1..4].iter().all(u8::is_ascii_digit) && bytes[4] == b' ' {
+        (name[1..4].parse().ok(), &name[5..])
+    } else {
+        (None, name)
+    }
+}
+
+pub(crate) fn strip_order_prefix(name: &str) -> &str {
+    split_order_prefix(name).1
+}
+
+pub(crate) fn order_prefix(priority: i32) -> String {
+    format!("#{:03} ", priority.clamp(0, MAX_PRIORITY))
+}
+
+// "DISABLED_Foo" -> ("DISABLED_", "Foo"), keeping the original case of the prefix
+pub(crate) fn split_disabled_prefix(name: &str) -> (&str, &str) {
+    let has = |prefix: &str| name.get(..prefix.len()).map(|h| h.eq_ignore_ascii_case(prefix)).unwrap_or(false);
+    if has("DISABLED_") {
+        name.split_at(9)
+    } else if has("DISABLED") && name.len() > 8 {
+        name.split_at(8)
+    } else {
+        ("", name)
+    }
+}
+
+// "Group/DISABLED_#050 Foo" -> "group/foo"
+pub(crate) fn priority_key(relative_path: &str) -> String {
+    relative_path
+        .split(['/', '\\'])
+        .filter(|c| !c.is_empty())
+        .map(|c| strip_order_prefix(split_disabled_prefix(c).1).to_lowercase())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn priorities_path(app: &AppHandle, game_name: &str) -> PathBuf {
+    get_global_games_dir(app).join(game_name).join(PRIORITIES_FILE)
+}
+
+pub(crate) fn load_priorities(app: &AppHandle, game_name: &str) -> BTreeMap<String, i32> {
+    fs::read_to_string(priorities_path(app, game_name))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_priorities(app: &AppHandle, game_name: &str, priorities: &BTreeMap<String, i32>) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(priorities)
+        .map_err(|e| format!("Failed to serialize mod priorities: {}", e))?;
+    fs::write(priorities_path(app, game_name), content).map_err(|e| format!("Failed to write mod priorities: {}", e))
+}
+
+// Move the keys of a renamed mod, or of every mod inside a renamed group
+fn retarget_keys(priorities: &mut BTreeMap<String, i32>, old_relative: &str, new_relative: &str) -> bool {
+    let old_key = priority_key(old_relative);
+    let new_key = priority_key(new_relative);
+    if old_key == new_key {
+        return false;
+    }
+    let moved: Vec<String> = priorities
+        .keys()
+        .filter(|k| **k == old_key || k.starts_with(&format!("{}/", old_key)))
+        .cloned()
+        .collect();
+    for key in &moved {
+        if let Some(priority) = priorities.remove(key) {
+            priorities.insert(format!("{}{}", new_key, &key[old_key.len()..]), priority);
+        }
+    }
+    !moved.is_empty()
+}
+
+// Entries of the mod at `relative`, or of the mods inside the group at `relative`,
+// as (rest of the key after it, priority)
+fn entries_under(priorities: &BTreeMap<String, i32>, relative: &str) -> Vec<(String, i32)> {
+    let key = priority_key(relative);
+    let group_prefix = format!("{}/", key);
+    priorities
+        .iter()
+        .filter_map(|(k, p)| {
+            if *k == key {
+                Some((String::new(), *p))
+            } else {
+                k.strip_prefix(&group_prefix).map(|rest| (format!("/{}", rest), *p))
+            }
+        })
+        .collect()
+}
+
+// Set or clear one mod's entry without reordering, for mods that arrive with a known priority
+pub(crate) fn record_priority(app: &AppHandle, game_name: &str, relative: &str, priority: Option<i32>) -> Result<(), String> {
+    let mut priorities = load_priorities(app, game_name);
+    match priority {
+        Some(p) => priorities.insert(priority_key(relative), p.clamp(0, MAX_PRIORITY)),
+        None => priorities.remove(&priority_key(relative)),
+    };
+    save_priorities(app, game_name, &priorities)
+}
+
+// Carry the priorities of a mod or group copied into another game, dropping them in the
+// source when it was moved
+pub(crate) fn transfer_priorities(
+    app: &AppHandle,
+    source_game: &str,
+    target_game: &str,
+    source_relative: &str,
+    target_relative: &str,
+    remove_source: bool,
+) -> Result<(), String> {
+    let mut source = load_priorities(app, source_game);
+    let entries = entries_under(&source, source_relative);
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let mut target = load_priorities(app, target_game);
+    let target_key = priority_key(target_relative);
+    for (rest, priority) in &entries {
+        target.insert(format!("{}{}", target_key, rest), *priority);
+    }
+    save_priorities(app, target_game, &target)?;
+
+    if remove_source {
+        let key = priority_key(source_relative);
+        for (rest, _) in entries {
+            source.remove(&format!("{}{}", key, rest));
+        }
+        save_priorities(app, source_game, &source)?;
+    }
+    Ok(())
+}
+
+// Keep priorities attached to mods renamed or moved inside Mods
+pub(crate) fn retarget_priorities(app: &AppHandle, game_name: &str, old_relative: &str, new_relative: &str) -> Result<(), String> {
+    let mut priorities = load_priorities(app, game_name);
+    if !retarget_keys(&mut priorities, old_relative, new_relative) {
+        return Ok(());
+    }
+    save_priorities(app, game_name, &priorities)
+}
+
+// Sort key matching 3DMigoto's case-insensitive path ordering
+fn load_order_key(relative_path: &str) -> String {
+    format!("{}\\", relative_path.replace('/', "\\").trim_matches('\\')).to_lowercase()
+}
+
+// Fill ModInfo::load_order for enabled mods, 0 loads first
+pub(crate) fn assign_load_order(mods: &mut [ModInfo]) {
+    let mut order: Vec<usize> = (0..mods.len()).filter(|&i| mods[i].enabled).collect();
+    order.sort_by_key(|&i| load_order_key(&mods[i].relative_path));
+    for (position, index) in order.into_iter().enumerate() {
+        mods[index].load_order = Some(position);
+    }
+}
+
+fn has_ini(dir: &Path) -> bool {
+    fs::read_dir(dir)
+        .map(|r| {
+            r.flatten().any(|e| {
+                e.path().is_file()
+                    && e.path()
+                        .extension()
+                        .map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("ini"))
+                        .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+// Name a mod folder should have: its priority prefix (if any) after the DISABLED_ prefix
+fn ordered_name(dir_name: &str, priority: Option<i32>) -> String {
+    let (disabled, rest) = split_disabled_prefix(dir_name);
+    let clean = strip_order_prefix(rest);
+    match priority {
+        Some(p) => format!("{}{}{}", disabled, order_prefix(p), clean),
+        None => format!("{}{}", disabled, clean),
+    }
+}
+
+// Rename the mod folders directly inside `dir` so their prefixes match their priorities.
+// Returns (old relative path, new relative path) for every renamed folder.
+fn apply_order_in_dir(
+    app: &AppHandle,
+    game_name: &str,
+    mods_dir: &Path,
+    dir: &Path,
+    priorities: &BTreeMap<String, i32>,
+) -> Result<Vec<(String, String)>, String> {
+    let mod_dirs: Vec<_> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {:?}: {}", dir, e))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir() && has_ini(p))
+        .collect();
+
+    let mut renamed = Vec::new();
+    for path in mod_dirs {
+        let dir_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let old_relative = path.strip_prefix(mods_dir).unwrap_or(&path).to_string_lossy().to_string();
+        let wanted = ordered_name(&dir_name, priorities.get(&priority_key(&old_relative)).copied());
+        if wanted == dir_name {
+            continue;
+        }
+
+        let new_path = path.with_file_name(&wanted);
+        if new_path.exists() {
+            return Err(format!("Cannot reorder {}: {} already exists", dir_name, wanted));
+        }
+        fs::rename(&path, &new_path).map_err(|e| format!("Failed to rename {}: {}", dir_name, e))?;
+
+        let new_relative = new_path.strip_prefix(mods_dir).unwrap_or(&new_path).to_string_lossy().to_string();
+        retarget_deployment(app, game_name, &old_relative, &new_relative)?;
+        println!("[ModLoadOrder] {} -> {}", old_relative, new_relative);
+        renamed.push((old_relative, new_relative));
+    }
+    Ok(renamed)
+}
+
+struct OrderWalk<'a> {
+    app: &'a AppHandle,
+    game_name: &'a str,
+    mods_dir: &'a Path,
+    priorities: &'a BTreeMap<String, i32>,
+    max_depth: usize,          // threeDMigoto.modScanDepth, same limit as scan_mods
+    visited: HashSet<PathBuf>, // Canonical paths, links back up the tree are walked once
+    renamed: usize,
+}
+
+fn apply_order_recursive(walk: &mut OrderWalk, dir: &Path, depth: usize) -> Result<(), String> {
+    if depth > walk.max_depth {
+        return Ok(());
+    }
+    let canonical = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    if !walk.visited.insert(canonical) {
+        println!("[ModLoadOrder] Skipping {:?}, already visited through a link", dir);
+        return Ok(());
+    }
+    walk.renamed += apply_order_in_dir(walk.app, walk.game_name, walk.mods_dir, dir, walk.priorities)?.len();
+
+    // Recurse into group folders only, mod folders are leaves
+    let groups: Vec<_> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {:?}: {}", dir, e))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir() && !has_ini(p))
+        .collect();
+    for group in groups {
+        apply_order_recursive(walk, &group, depth + 1)?;
+    }
+    Ok(())
+}
+
+// Set (or clear with None) a mod's priority and reorder its siblings. Returns the mod's new relative path.
+#[tauri::command]
+pub fn set_mod_priority(app: AppHandle, game_name: String, mod_relative_path: String, priority: Option<i32>) -> Result<String, String> {
+    if let Some(p) = priority {
+        if !(0..=MAX_PRIORITY).contains(&p) {
+            return Err(format!("Priority must be between 0 and {}", MAX_PRIORITY));
+        }
+    }
+
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    let mod_dir = resolve_mod_path(&mods_dir, &mod_relative_path)?;
+    if !mod_dir.is_dir() {
+        return Err("Mod directory not found".to_string());
+    }
+
+    let current = mod_dir.strip_prefix(&mods_dir).unwrap_or(&mod_dir).to_string_lossy().to_string();
+    let mut priorities = load_priorities(&app, &game_name);
+    match priority {
+        Some(p) => priorities.insert(priority_key(&current), p),
+        None => priorities.remove(&priority_key(&current)),
+    };
+    save_priorities(&app, &game_name, &priorities)?;
+
+    let parent = mod_dir.parent().ok_or("Invalid mod path")?;
+    let renamed = apply_order_in_dir(&app, &game_name, &mods_dir, parent, &priorities)?;
+    Ok(renamed
+        .into_iter()
+        .find(|(old, _)| *old == current)
+        .map(|(_, new)| new)
+        .unwrap_or(current))
+}
+
+// Re-apply prefixes everywhere, e.g. after installing or moving mods. Returns the number of renamed folders.
+#[tauri::command]
+pub fn apply_mod_load_order(app: AppHandle, game_name: String) -> Result<usize, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    if !mods_dir.exists() {
+        return Err("Mods directory not found".to_string());
+    }
+    let priorities = load_priorities(&app, &game_name);
+    let mut walk = OrderWalk {
+        app: &app,
+        game_name: &game_name,
+        mods_dir: &mods_dir,
+        priorities: &priorities,
+        max_depth: get_mod_scan_depth(&app, &game_name),
+        visited: HashSet::new(),
+        renamed: 0,
+    };
+    apply_order_recursive(&mut walk, &mods_dir, 0)?;
+    Ok(walk.renamed)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideClaim {
+    pub mod_relative_path: String,
+    pub ini_path: String,
+    pub section: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModConflict {
+    pub hash: String,
+    pub match_first_index: Option<String>,
+    pub winner: String, // Mod loaded last, its override is the one visible in game
+    pub claims: Vec<OverrideClaim>, // In load order
+}
+
+// (hash, match_first_index) -> (load order key, claim)
+type OverrideClaims = BTreeMap<(String, Option<String>), Vec<(String, OverrideClaim)>>;
+
+// TextureOverride sections of different enabled mods that target the same hash
+pub(crate) fn find_override_conflicts(mods_dir: &Path, scan_depth: usize) -> Vec<ModConflict> {
+    let mut claims: OverrideClaims = BTreeMap::new();
+
+    for file in collect_mod_inis(mods_dir, scan_depth).into_iter().filter(|f| f.enabled) {
+        let ini = match ModIni::load(&file.ini_path) {
+            Ok(ini) => ini,
+            Err(_) => continue,
+        };
+        let relative_ini = file.ini_path.strip_prefix(mods_dir).unwrap_or(&file.ini_path).to_string_lossy().to_string();
+
+        for section in ini.sections_with_prefix("TextureOverride") {
+            let hash = match section.get("hash") {
+                Some(h) => h.trim().to_lowercase(),
+                None => continue,
+            };
+            let first_index = section.get("match_first_index").map(|v| v.trim().to_string());
+            claims.entry((hash, first_index)).or_default().push((
+                load_order_key(&relative_ini),
+                OverrideClaim {
+                    mod_relative_path: file.mod_relative_path.clone(),
+                    ini_path: file.ini_path.to_string_lossy().to_string(),
+                    section: section.name.clone(),
+                    line: section.line,
+                },
+            ));
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    for ((hash, match_first_index), mut entries) in claims {
+        let mut mods: Vec<&str> = entries.iter().map(|(_, c)| c.mod_relative_path.as_str()).collect();
+        mods.sort();
+        mods.dedup();
+        if mods.len() < 2 {
+            continue;
+        }
+
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        let claims: Vec<OverrideClaim> = entries.into_iter().map(|(_, c)| c).collect();
+        conflicts.push(ModConflict {
+            hash,
+            match_first_index,
+            winner: claims.last().map(|c| c.mod_relative_path.clone()).unwrap_or_default(),
+            claims,
+        });
+    }
+    conflicts
+}
+
+#[tauri::command]
+pub fn detect_mod_conflicts(app: AppHandle, game_name: String) -> Result<Vec<ModConflict>, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    if !mods_dir.exists() {
+        return Err("Mods directory not found".to_string());
+    }
+    Ok(find_override_conflicts(&mods_dir, get_mod_scan_depth(&app, &game_name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_prefixes() {
+        assert_eq!(split_order_prefix("#050 Foo"), (Some(50), "Foo"));
+        assert_eq!(split_order_prefix("#05 Foo"), (None, "#05 Foo"));
+        assert_eq!(split_disabled_prefix("disabled_Foo"), ("disabled_", "Foo"));
+        assert_eq!(split_disabled_prefix("DISABLEDFoo"), ("DISABLED", "Foo"));
+        assert_eq!(split_disabled_prefix("DISABLED"), ("", "DISABLED"));
+    }
+
+    #[test]
+    fn priority_key_ignores_prefixes() {
+        assert_eq!(priority_key("Chars\\DISABLED_#050 Foo"), "chars/foo");
+        assert_eq!(priority_key("DISABLED_Chars/#999 Foo/"), "chars/foo");
+        assert_eq!(priority_key("Foo"), priority_key("#001 foo"));
+    }
+
+    #[test]
+    fn only_prioritized_mods_get_a_prefix() {
+        assert_eq!(ordered_name("Foo", None), "Foo");
+        assert_eq!(ordered_name("#500 Foo", None), "Foo");
+        assert_eq!(ordered_name("Foo", Some(7)), "#007 Foo");
+        assert_eq!(ordered_name("DISABLED_#500 Foo", Some(600)), "DISABLED_#600 Foo");
+    }
+
+    #[test]
+    fn finds_entries_of_a_mod_or_group() {
+        let priorities = BTreeMap::from([
+            ("chars/foo".to_string(), 10),
+            ("chars/foo/bar".to_string(), 20),
+            ("chars/foobar".to_string(), 30),
+        ]);
+        assert_eq!(entries_under(&priorities, "Chars/#010 Foo"), vec![(String::new(), 10), ("/bar".to_string(), 20)]);
+        assert_eq!(entries_under(&priorities, "Chars"), vec![("/foo".to_string(), 10), ("/foo/bar".to_string(), 20), ("/foobar".to_string(), 30)]);
+        assert!(entries_under(&priorities, "Other").is_empty());
+    }
+
+    #[test]
+    fn retargets_mods_and_groups() {
+        let mut priorities = BTreeMap::from([
+            ("chars/foo".to_string(), 10),
+            ("chars/bar".to_string(), 20),
+            ("charsx/baz".to_string(), 30),
+        ]);
+        assert!(retarget_keys(&mut priorities, "Chars", "People"));
+        assert_eq!(priorities.get("people/foo"), Some(&10));
+        assert_eq!(priorities.get("people/bar"), Some(&20));
+        assert_eq!(priorities.get("charsx/baz"), Some(&30));
+
+        assert!(retarget_keys(&mut priorities, "People/#010 Foo", "Other/Foo"));
+        assert_eq!(priorities.get("other/foo"), Some(&10));
+        // Toggling or reordering keeps the key
+        assert!(!retarget_keys(&mut priorities, "Other/Foo", "Other/DISABLED_#010 Foo"));
+    }
+}
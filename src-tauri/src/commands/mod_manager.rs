@@ -1,10 +1,14 @@
 use crate::utils::file_manager::get_global_games_dir;
 use serde::{Deserialize, Serialize};
+use crate::commands::mod_load_order::{load_priorities, priority_key};
+use crate::utils::mod_paths::{check_file_name, resolve_mod_path, resolve_under};
+use crate::utils::platform::{delete_permanently, move_to_trash};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Emitter, State};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher, Config};
 use std::sync::Mutex;
+use std::collections::{BTreeMap, HashSet};
 
 fn decode_zip_name(file: &zip::read::ZipFile) -> String {
     let raw = file.name_raw();
@@ -30,6 +34,14 @@ pub struct GroupInfo {
     pub enabled: bool, // Based on whether the folder itself (or parent) is disabled
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModVariant {
+    pub name: String,          // File name without DISABLED_
+    pub relative_path: String, // Relative to the mod folder, e.g. "Variants/DISABLED_Red.ini"
+    pub enabled: bool,         // false if the file or a folder inside the mod is DISABLED_
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModInfo {
@@ -42,6 +54,10 @@ pub struct ModInfo {
     pub group: String,      // Parent folder name if depth > 1
     pub is_dir: bool,
     pub last_modified: u64, // Timestamp
+    pub library_id: Option<String>, // Set when this folder is a link deployed from the mod library
+    pub priority: Option<i32>,     // From ModPriorities.json, None = not ordered
+    pub load_order: Option<usize>, // Position in 3DMigoto's load order, None when disabled
+    pub variants: Vec<ModVariant>, // Every .ini inside the mod
 }
 
 #[derive(Deserialize)]
@@ -54,9 +70,14 @@ struct GameConfigPartial {
 struct ThreeDMigotoPartial {
     #[serde(rename = "installDir")]
     install_dir: Option<String>,
+    #[serde(rename = "modScanDepth")]
+    mod_scan_depth: Option<usize>,
 }
 
-fn get_game_install_dir(app: &AppHandle, game_name: &str) -> Result<PathBuf, String> {
+pub const DEFAULT_MOD_SCAN_DEPTH: usize = 5;
+const MAX_MOD_SCAN_DEPTH: usize = 32;
+
+fn load_config_partial(app: &AppHandle, game_name: &str) -> Result<GameConfigPartial, String> {
     let games_dir = get_global_games_dir(app);
     let config_path = games_dir.join(game_name).join("Config.json");
 
@@ -67,8 +88,12 @@ fn get_game_install_dir(app: &AppHandle, game_name: &str) -> Result<PathBuf, Str
     let config_content = fs::read_to_string(&config_path)
         .map_err(|e| format!("Failed to read config: {}", e))?;
 
-    let config: GameConfigPartial = serde_json::from_str(&config_content)
-        .map_err(|e| format!("Failed to parse config: {}", e))?;
+    serde_json::from_str(&config_content)
+        .map_err(|e| format!("Failed to parse config: {}", e))
+}
+
+pub(crate) fn get_game_install_dir(app: &AppHandle, game_name: &str) -> Result<PathBuf, String> {
+    let config = load_config_partial(app, game_name)?;
 
     if let Some(tdm) = config.three_d_migoto {
         if let Some(dir) = tdm.install_dir {
@@ -79,7 +104,17 @@ fn get_game_install_dir(app: &AppHandle, game_name: &str) -> Result<PathBuf, Str
     Err("3Dmigoto install directory not configured".to_string())
 }
 
-fn find_preview_images(path: &Path) -> Vec<String> {
+// threeDMigoto.modScanDepth from the game config, how many folder levels below Mods are scanned
+pub(crate) fn get_mod_scan_depth(app: &AppHandle, game_name: &str) -> usize {
+    load_config_partial(app, game_name)
+        .ok()
+        .and_then(|c| c.three_d_migoto)
+        .and_then(|t| t.mod_scan_depth)
+        .unwrap_or(DEFAULT_MOD_SCAN_DEPTH)
+        .clamp(1, MAX_MOD_SCAN_DEPTH)
+}
+
+pub(crate) fn find_preview_images(path: &Path) -> Vec<String> {
     let mut images = Vec::new();
     if let Ok(entries) = fs::read_dir(path) {
         for entry in entries.flatten() {
@@ -101,18 +136,66 @@ fn find_preview_images(path: &Path) -> Vec<String> {
     images
 }
 
+// Every .ini inside a mod folder, with its own DISABLED_ state
+fn scan_mod_variants(mod_dir: &Path, max_depth: usize) -> Vec<ModVariant> {
+    crate::utils::mod_ini::collect_variant_inis(mod_dir, max_depth)
+        .into_iter()
+        .map(|f| {
+            let file_name = f.ini_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            ModVariant {
+                name: crate::commands::mod_load_order::split_disabled_prefix(&file_name).1.to_string(),
+                relative_path: f.ini_path.strip_prefix(mod_dir).unwrap_or(&f.ini_path).to_string_lossy().to_string(),
+                enabled: f.enabled,
+            }
+        })
+        .collect()
+}
+
+pub use crate::utils::mod_ini::{TruncateReason, TruncatedScan};
+
+pub(crate) struct ScanState {
+    pub mods: Vec<ModInfo>,
+    pub groups: Vec<GroupInfo>,
+    pub truncated: Vec<TruncatedScan>,
+    pub priorities: BTreeMap<String, i32>, // Load order priorities by priority_key, only needed by scan_mods
+    max_depth: usize,
+    visited: HashSet<PathBuf>, // Canonical paths of scanned folders
+}
+
+impl ScanState {
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            mods: Vec::new(),
+            groups: Vec::new(),
+            truncated: Vec::new(),
+            priorities: BTreeMap::new(),
+            max_depth,
+            visited: HashSet::new(),
+        }
+    }
+}
+
 // Recursive scan function
-// depth: current depth. max_depth: how deep to go.
-fn scan_folder(
+// depth: current depth, state.max_depth: how deep to go.
+pub(crate) fn scan_folder(
     base_mods_dir: &Path, 
     current_dir: &Path, 
-    results: &mut Vec<ModInfo>, 
     current_group: String,
     depth: usize,
-    groups_found: &mut Vec<GroupInfo>,
     parent_disabled: bool,
+    state: &mut ScanState,
 ) {
-    if depth > 5 { return; } // Limit depth increased for multi-level
+    let relative_dir = || current_dir.strip_prefix(base_mods_dir).unwrap_or(current_dir).to_string_lossy().to_string();
+    if depth > state.max_depth {
+        state.truncated.push(TruncatedScan { relative_path: relative_dir(), reason: TruncateReason::DepthLimit });
+        return;
+    }
+    // Symlinks/junctions can point back up the tree, scan every real folder once
+    let canonical = current_dir.canonicalize().unwrap_or_else(|_| current_dir.to_path_buf());
+    if !state.visited.insert(canonical) {
+        state.truncated.push(TruncatedScan { relative_path: relative_dir(), reason: TruncateReason::SymlinkLoop });
+        return;
+    }
 
     if let Ok(entries) = fs::read_dir(current_dir) {
         for entry in entries.flatten() {
@@ -142,6 +225,8 @@ fn scan_folder(
                 } else {
                     dir_name.clone()
                 };
+                // Hide the managed load-order prefix ("#050 ")
+                let clean_name = crate::commands::mod_load_order::strip_order_prefix(&clean_name).to_string();
                 
                 // Relative path for ID/Storage
                 let relative_path = path.strip_prefix(base_mods_dir).unwrap_or(&path).to_string_lossy().to_string();
@@ -199,7 +284,7 @@ fn scan_folder(
                         .map(|d| d.as_secs())
                         .unwrap_or(0);
                         
-                     results.push(ModInfo {
+                     state.mods.push(ModInfo {
                         id: relative_path.clone(),
                         name: clean_name.clone(),
                         enabled: !effective_disabled, // Mod is enabled only if self AND all parents are enabled
@@ -209,6 +294,10 @@ fn scan_folder(
                         group: current_group.clone(),
                         is_dir: true,
                         last_modified,
+                        library_id: None,
+                        priority: state.priorities.get(&priority_key(&relative_path)).copied(),
+                        load_order: None,
+                        variants: scan_mod_variants(&path, state.max_depth),
                     });
                 } else {
                     // It is a category folder
@@ -234,7 +323,7 @@ fn scan_folder(
                         }
                     }
 
-                    groups_found.push(GroupInfo {
+                    state.groups.push(GroupInfo {
                          id: next_group.clone(),
                          name: clean_name.clone(),
                          icon_path: icon,
@@ -243,7 +332,7 @@ fn scan_folder(
                     });
 
                     // Recurse
-                    scan_folder(base_mods_dir, &path, results, next_group, depth + 1, groups_found, effective_disabled);
+                    scan_folder(base_mods_dir, &path, next_group, depth + 1, effective_disabled, state);
                 }
             }
         }
@@ -253,7 +342,7 @@ fn scan_folder(
 #[tauri::command]
 pub async fn set_mod_group_icon(app: AppHandle, game_name: String, group_path: String, icon_path: String) -> Result<(), String> {
     let install_dir = get_game_install_dir(&app, &game_name)?;
-    let dest_dir = install_dir.join("Mods").join(&group_path);
+    let dest_dir = resolve_mod_path(&install_dir.join("Mods"), &group_path)?;
 
     if !dest_dir.exists() {
         return Err("Group directory not found".to_string());
@@ -282,7 +371,7 @@ pub async fn set_mod_group_icon(app: AppHandle, game_name: String, group_path: S
 #[tauri::command]
 pub async fn open_mod_group_folder(app: AppHandle, game_name: String, group_path: String) -> Result<(), String> {
     let install_dir = get_game_install_dir(&app, &game_name)?;
-    let dest_dir = install_dir.join("Mods").join(&group_path);
+    let dest_dir = resolve_mod_path(&install_dir.join("Mods"), &group_path)?;
 
     if !dest_dir.exists() {
         return Err("Group directory not found".to_string());
@@ -307,6 +396,7 @@ pub async fn open_game_mods_folder(app: AppHandle, game_name: String) -> Result<
 pub struct ModScanResult {
     pub mods: Vec<ModInfo>,
     pub groups: Vec<GroupInfo>,
+    pub truncated: Vec<TruncatedScan>, // Subtrees that were not scanned
 }
 
 #[tauri::command]
@@ -320,23 +410,35 @@ pub async fn scan_mods(app: AppHandle, game_name: String) -> Result<ModScanResul
             return Err(format!("Failed to create Mods directory at {:?}: {}", mods_dir, e));
         }
         // If just created, it's empty
-        return Ok(ModScanResult { mods: Vec::new(), groups: Vec::new() });
+        return Ok(ModScanResult { mods: Vec::new(), groups: Vec::new(), truncated: Vec::new() });
     }
 
-    let mut mods = Vec::new();
-    let mut groups_list = Vec::new();
-
     // 1. Scan for mods recursively
-    scan_folder(&mods_dir, &mods_dir, &mut mods, "Root".to_string(), 0, &mut groups_list, false);
+    let mut state = ScanState::new(get_mod_scan_depth(&app, &game_name));
+    state.priorities = load_priorities(&app, &game_name);
+    scan_folder(&mods_dir, &mods_dir, "Root".to_string(), 0, false, &mut state);
+    let ScanState { mut mods, groups: mut groups_list, truncated, .. } = state;
+    for t in &truncated {
+        println!("[ModManager] Scan stopped at {} ({:?})", t.relative_path, t.reason);
+    }
     
     // Sort groups by id
     groups_list.sort_by(|a, b| a.id.cmp(&b.id));
 
+    // Mark folders that are links into the shared mod library
+    let deployments = crate::commands::mod_library::load_deployments(&app, &game_name);
+    for m in mods.iter_mut() {
+        m.library_id = crate::commands::mod_library::find_deployment_in(&deployments, &m.relative_path)
+            .map(|d| d.library_id.clone());
+    }
+    crate::commands::mod_load_order::assign_load_order(&mut mods);
+
     // Dedup groups (scan_folder might visit same node? No, it's tree traversal, unique nodes)
     
     Ok(ModScanResult {
         mods,
-        groups: groups_list
+        groups: groups_list,
+        truncated,
     })
 }
 
@@ -350,11 +452,19 @@ pub async fn toggle_mod(app: AppHandle, game_name: String, mod_relative_path: St
     // However, logic here is tricky because the path on disk *changes* when we rename it.
     // The `mod_relative_path` sent by frontend corresponds to the *current* state.
     
-    let current_full_path = mods_dir.join(&mod_relative_path);
+    let current_full_path = resolve_mod_path(&mods_dir, &mod_relative_path)?;
     if !current_full_path.exists() {
         return Err("Mod directory not found".to_string());
     }
 
+    // Library mods are disabled by removing their link instead of renaming
+    if let Some(deployment) = crate::commands::mod_library::find_deployment(&app, &game_name, &mod_relative_path) {
+        if !enable {
+            crate::commands::mod_library::undeploy_library_mod(app, game_name, deployment.library_id)?;
+        }
+        return Ok(current_full_path.to_string_lossy().to_string());
+    }
+
     let parent = current_full_path.parent().ok_or("Invalid path")?;
     let dirname = current_full_path.file_name().ok_or("Invalid filename")?.to_string_lossy().to_string();
 
@@ -384,6 +494,75 @@ pub async fn toggle_mod(app: AppHandle, game_name: String, mod_relative_path: St
     Ok(new_full_path.to_string_lossy().to_string())
 }
 
+#[tauri::command]
+pub fn list_mod_variants(app: AppHandle, game_name: String, mod_relative_path: String) -> Result<Vec<ModVariant>, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    let mod_dir = resolve_mod_path(&mods_dir, &mod_relative_path)?;
+    if !mod_dir.is_dir() {
+        return Err("Mod directory not found".to_string());
+    }
+    Ok(scan_mod_variants(&mod_dir, get_mod_scan_depth(&app, &game_name)))
+}
+
+// Enable/disable one .ini of a mod by renaming it with the DISABLED_ prefix.
+// exclusive: when enabling, disable the other .ini files in the same folder (pick one variant).
+// Returns the new relative path of the variant.
+#[tauri::command]
+pub fn toggle_mod_variant(
+    app: AppHandle,
+    game_name: String,
+    mod_relative_path: String,
+    variant_path: String,
+    enable: bool,
+    exclusive: Option<bool>,
+) -> Result<String, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    let mod_dir = resolve_mod_path(&mods_dir, &mod_relative_path)?;
+    let current = resolve_under(&mod_dir, &variant_path)?;
+    if !current.extension().map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("ini")).unwrap_or(false) {
+        return Err(format!("{} is not an .ini file", variant_path));
+    }
+    if !current.is_file() {
+        return Err("Variant file not found".to_string());
+    }
+
+    let rename_ini = |path: &Path, enable: bool| -> Result<PathBuf, String> {
+        let file_name = path.file_name().ok_or("Invalid filename")?.to_string_lossy().to_string();
+        let (prefix, clean) = crate::commands::mod_load_order::split_disabled_prefix(&file_name);
+        let new_name = match (enable, prefix.is_empty()) {
+            (true, false) => clean.to_string(),
+            (false, true) => format!("DISABLED_{}", file_name),
+            _ => return Ok(path.to_path_buf()), // Already in the requested state
+        };
+        let new_path = path.with_file_name(&new_name);
+        if new_path.exists() {
+            return Err(format!("{} already exists", new_name));
+        }
+        fs::rename(path, &new_path).map_err(|e| format!("Failed to rename {}: {}", file_name, e))?;
+        Ok(new_path)
+    };
+
+    if enable && exclusive.unwrap_or(false) {
+        let folder = current.parent().ok_or("Invalid path")?;
+        let siblings: Vec<PathBuf> = fs::read_dir(folder)
+            .map_err(|e| format!("Failed to read mod folder: {}", e))?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                *p != current
+                    && p.is_file()
+                    && p.extension().map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("ini")).unwrap_or(false)
+            })
+            .collect();
+        for sibling in siblings {
+            rename_ini(&sibling, false)?;
+        }
+    }
+
+    let new_path = rename_ini(&current, enable)?;
+    Ok(new_path.strip_prefix(&mod_dir).unwrap_or(&new_path).to_string_lossy().to_string())
+}
+
 #[tauri::command]
 pub async fn watch_mods(app: AppHandle, state: State<'_, ModWatcher>, game_name: String) -> Result<(), String> {
     let install_dir = get_game_install_dir(&app, &game_name)?;
@@ -395,7 +574,7 @@ pub async fn watch_mods(app: AppHandle, state: State<'_, ModWatcher>, game_name:
     
     // Stop existing watcher
     let mut watcher_guard = state.0.lock().unwrap();
-    if let Some(_) = *watcher_guard {
+    if watcher_guard.is_some() {
         // Drop old watcher
         *watcher_guard = None;
     }
@@ -446,7 +625,7 @@ pub async fn toggle_mod_group(app: AppHandle, game_name: String, group_path: Str
     // group_path is relative to Mods directory (e.g. "Group A" or "DISABLED_Group A")
     // Frontend should pass the full relative path stored in field `GroupInfo.path`
     
-    let current_full_path = mods_dir.join(&group_path);
+    let current_full_path = resolve_mod_path(&mods_dir, &group_path)?;
     if !current_full_path.exists() {
         return Err("Group directory not found".to_string());
     }
@@ -491,13 +670,15 @@ pub async fn toggle_mod_group(app: AppHandle, game_name: String, group_path: Str
 
     // Return the new relative path
     let new_relative = new_full_path.strip_prefix(&mods_dir).unwrap_or(&new_full_path).to_string_lossy().to_string();
+    crate::commands::mod_library::retarget_deployment(&app, &game_name, &group_path, &new_relative)?;
     Ok(new_relative)
 }
 
 #[tauri::command]
 pub fn create_mod_group(app: AppHandle, game_name: String, group_name: String) -> Result<(), String> {
     let install_dir = get_game_install_dir(&app, &game_name)?;
-    let group_dir = install_dir.join("Mods").join(&group_name);
+    fs::create_dir_all(install_dir.join("Mods")).map_err(|e| format!("Failed to create Mods directory: {}", e))?;
+    let group_dir = resolve_mod_path(&install_dir.join("Mods"), &group_name)?;
     
     if group_dir.exists() {
         return Err("Group already exists".to_string());
@@ -511,8 +692,8 @@ pub fn create_mod_group(app: AppHandle, game_name: String, group_name: String) -
 pub async fn rename_mod_group(app: AppHandle, state: State<'_, ModWatcher>, game_name: String, old_group: String, new_group: String) -> Result<(), String> {
     let install_dir = get_game_install_dir(&app, &game_name)?;
     let mods_dir = install_dir.join("Mods");
-    let old_dir = mods_dir.join(&old_group);
-    let new_dir = mods_dir.join(&new_group);
+    let old_dir = resolve_mod_path(&mods_dir, &old_group)?;
+    let new_dir = resolve_mod_path(&mods_dir, &new_group)?;
     
     if !old_dir.exists() {
         return Err("Old group does not exist".to_string());
@@ -554,8 +735,10 @@ pub async fn rename_mod_group(app: AppHandle, state: State<'_, ModWatcher>, game
     if !success {
         return Err(format!("Failed to rename group (Occupied): {}. Please close Explorer/Files.", last_error));
     }
-    
-    Ok(())
+
+    // Library links and priorities inside the group moved along with it
+    crate::commands::mod_load_order::retarget_priorities(&app, &game_name, &old_group, &new_group)?;
+    crate::commands::mod_library::retarget_deployment(&app, &game_name, &old_group, &new_group)
 }
 
 #[tauri::command]
@@ -564,7 +747,8 @@ pub fn rename_mod(app: AppHandle, game_name: String, mod_path: String, new_name:
     let mods_dir = install_dir.join("Mods");
     
     // mod_path is relative to Mods dir, e.g., "Group/ModA" or "ModB"
-    let old_full_path = mods_dir.join(&mod_path);
+    let old_full_path = resolve_mod_path(&mods_dir, &mod_path)?;
+    let new_name = check_file_name(&new_name)?.to_string();
     
     if !old_full_path.exists() {
         return Err("Mod does not exist".to_string());
@@ -591,6 +775,17 @@ pub fn rename_mod(app: AppHandle, game_name: String, mod_path: String, new_name:
     // Let's check if the current folder starts with DISABLED_.
     let old_folder_name = old_full_path.file_name().unwrap().to_string_lossy().to_string();
     let is_disabled = old_folder_name.to_uppercase().starts_with("DISABLED");
+
+    // Keep the managed load-order prefix, the user only edits the visible name
+    let (_, old_rest) = crate::commands::mod_load_order::split_disabled_prefix(&old_folder_name);
+    let new_name = match crate::commands::mod_load_order::split_order_prefix(old_rest).0 {
+        Some(priority) if !new_name.to_uppercase().starts_with("DISABLED") => format!(
+            "{}{}",
+            crate::commands::mod_load_order::order_prefix(priority),
+            crate::commands::mod_load_order::strip_order_prefix(&new_name)
+        ),
+        _ => new_name,
+    };
     
     let final_new_name = if is_disabled {
         // If it was disabled, we persist the disabled state
@@ -617,6 +812,10 @@ pub fn rename_mod(app: AppHandle, game_name: String, mod_path: String, new_name:
     }
 
     fs::rename(&old_full_path, &new_full_path).map_err(|e| format!("Failed to rename mod: {}", e))?;
+
+    let new_relative = new_full_path.strip_prefix(&mods_dir).unwrap_or(&new_full_path).to_string_lossy().to_string();
+    crate::commands::mod_load_order::retarget_priorities(&app, &game_name, &mod_path, &new_relative)?;
+    crate::commands::mod_library::retarget_deployment(&app, &game_name, &mod_path, &new_relative)?;
     Ok(())
 }
 
@@ -624,7 +823,7 @@ pub fn rename_mod(app: AppHandle, game_name: String, mod_path: String, new_name:
 pub fn add_mod_preview_images(app: AppHandle, game_name: String, mod_path: String, image_paths: Vec<String>) -> Result<(), String> {
     let install_dir = get_game_install_dir(&app, &game_name)?;
     let mods_dir = install_dir.join("Mods");
-    let target_mod_dir = mods_dir.join(&mod_path);
+    let target_mod_dir = resolve_mod_path(&mods_dir, &mod_path)?;
 
     if !target_mod_dir.exists() {
         return Err("Mod directory does not exist".to_string());
@@ -649,108 +848,41 @@ pub fn add_mod_preview_images(app: AppHandle, game_name: String, mod_path: Strin
 }
 
 #[tauri::command]
-pub fn delete_mod_group(app: AppHandle, game_name: String, group_name: String) -> Result<(), String> {
+pub fn delete_mod_group(app: AppHandle, game_name: String, group_name: String, permanent: Option<bool>) -> Result<(), String> {
     let install_dir = get_game_install_dir(&app, &game_name)?;
-    let group_dir = install_dir.join("Mods").join(&group_name);
+    let group_dir = resolve_mod_path(&install_dir.join("Mods"), &group_name)?;
     
     if !group_dir.exists() {
         return Err("Group does not exist".to_string());
     }
     
-    // Recycle Bin via PowerShell (Windows only)
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-
-        let path_str = group_dir.to_string_lossy().to_string();
-        // Use VisualBasic.FileIO.FileSystem.DeleteDirectory for generic recycled bin deletion
-        // Needs absolute path
-        let abs_path = if group_dir.is_absolute() { 
-            path_str 
-        } else {
-             // Should verify it is absolute, get_game_install_dir returns absolute
-             path_str
-        };
-
-        let ps_script = format!(
-            "Add-Type -AssemblyName Microsoft.VisualBasic; [Microsoft.VisualBasic.FileIO.FileSystem]::DeleteDirectory('{}', 'OnlyErrorDialogs', 'SendToRecycleBin')",
-            abs_path.replace("'", "''") // escape quotes
-        );
-        
-        let status = std::process::Command::new("powershell")
-            .args(["-NoProfile", "-Command", &ps_script])
-            .creation_flags(CREATE_NO_WINDOW)
-            .status()
-            .map_err(|e| format!("Failed to run recycle bin command: {}", e))?;
-
-        if !status.success() {
-            return Err("Failed to move to recycle bin".to_string());
-        }
+    // Recycle Bin on Windows, trash on Linux. permanent: the user agreed after the trash failed
+    if permanent.unwrap_or(false) {
+        return delete_permanently(&group_dir);
     }
-
-    #[cfg(not(target_os = "windows"))]
-    {
-        // Fallback for non-windows
-        fs::remove_dir_all(&group_dir).map_err(|e| e.to_string())?;
-    }
-    
-    Ok(())
+    move_to_trash(&group_dir)
 }
 
 #[tauri::command]
-pub fn delete_mod(app: AppHandle, game_name: String, mod_relative_path: String) -> Result<(), String> {
+pub fn delete_mod(app: AppHandle, game_name: String, mod_relative_path: String, permanent: Option<bool>) -> Result<(), String> {
     let install_dir = get_game_install_dir(&app, &game_name)?;
     let mods_dir = install_dir.join("Mods");
-    let target_path = mods_dir.join(&mod_relative_path);
+    let target_path = resolve_mod_path(&mods_dir, &mod_relative_path)?;
     
     if !target_path.exists() {
         return Err("Mod path does not exist".to_string());
     }
-    
-    // Recycle Bin via PowerShell (Windows only)
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-        let path_str = target_path.to_string_lossy().to_string();
-        let abs_path = if target_path.is_absolute() { 
-            path_str 
-        } else {
-             path_str
-        };
-        
-        let is_dir = target_path.is_dir();
-        let method = if is_dir { "DeleteDirectory" } else { "DeleteFile" };
-
-        let ps_script = format!(
-            "Add-Type -AssemblyName Microsoft.VisualBasic; [Microsoft.VisualBasic.FileIO.FileSystem]::{}('{}', 'OnlyErrorDialogs', 'SendToRecycleBin')",
-            method,
-            abs_path.replace("'", "''") 
-        );
-        
-        let status = std::process::Command::new("powershell")
-            .args(["-NoProfile", "-Command", &ps_script])
-            .creation_flags(CREATE_NO_WINDOW)
-            .status()
-            .map_err(|e| format!("Failed to run recycle bin command: {}", e))?;
-
-        if !status.success() {
-            return Err("Failed to move to recycle bin".to_string());
-        }
-    }
-
-    #[cfg(not(target_os = "windows"))]
-    {
-        if target_path.is_dir() {
-            fs::remove_dir_all(&target_path).map_err(|e| e.to_string())?;
-        } else {
-            fs::remove_file(&target_path).map_err(|e| e.to_string())?;
-        }
+    // Deleting a library mod from Mods only removes the link, the library copy stays
+    if let Some(deployment) = crate::commands::mod_library::find_deployment(&app, &game_name, &mod_relative_path) {
+        return crate::commands::mod_library::undeploy_library_mod(app, game_name, deployment.library_id);
     }
     
-    Ok(())
+    // Recycle Bin on Windows, trash on Linux
+    if permanent.unwrap_or(false) {
+        return delete_permanently(&target_path);
+    }
+    move_to_trash(&target_path)
 }
 
 #[tauri::command]
@@ -759,7 +891,7 @@ pub fn move_mod_to_group(app: AppHandle, game_name: String, mod_id: String, new_
     let mods_dir = install_dir.join("Mods");
     
     // mod_id is the relative path from "Mods" folder, e.g. "Raiden/RaidenMod1" or "RaidenMod1"
-    let src_path = mods_dir.join(&mod_id);
+    let src_path = resolve_mod_path(&mods_dir, &mod_id)?;
     if !src_path.exists() {
         return Err(format!("Mod not found at {:?}", src_path));
     }
@@ -780,7 +912,7 @@ pub fn move_mod_to_group(app: AppHandle, game_name: String, mod_id: String, new_
         new_group
     };
 
-    let dest_parent = mods_dir.join(&target_group);
+    let dest_parent = resolve_mod_path(&mods_dir, &target_group)?;
     
     if !dest_parent.exists() {
         // Create group if it doesn't exist (e.g. dragging to a new group name provided by UI?)
@@ -798,7 +930,10 @@ pub fn move_mod_to_group(app: AppHandle, game_name: String, mod_id: String, new_
     // Note: fs::rename might fail across different mount points, but Mods folder is usually one drive.
     // If it fails, we might need copy+delete, but that's rarer for this use case.
     fs::rename(&src_path, &dest_path).map_err(|e| format!("Failed to move mod: {}", e))?;
-    
+
+    let new_relative = dest_path.strip_prefix(&mods_dir).unwrap_or(&dest_path).to_string_lossy().to_string();
+    crate::commands::mod_load_order::retarget_priorities(&app, &game_name, &mod_id, &new_relative)?;
+    crate::commands::mod_library::retarget_deployment(&app, &game_name, &mod_id, &new_relative)?;
     Ok(())
 }
 
@@ -947,6 +1082,7 @@ pub async fn install_mod_archive(
 ) -> Result<(), String> {
     let install_dir = get_game_install_dir(&app, &game_name)?;
     let mods_dir = install_dir.join("Mods");
+    fs::create_dir_all(&mods_dir).map_err(|e| format!("Failed to create Mods directory: {}", e))?;
     
     // Determine effective target directory
     // If target_group is "Root" or empty, install directly to Mods/target_name
@@ -954,9 +1090,9 @@ pub async fn install_mod_archive(
     
     let dest_dir = if target_group == "Root" || target_group.is_empty() {
         // Force "Default" group if none specified, to avoid polluting root
-        mods_dir.join("Default").join(&target_name)
+        resolve_mod_path(&mods_dir, "Default")?.join(check_file_name(&target_name)?)
     } else {
-        mods_dir.join(&target_group).join(&target_name)
+        resolve_mod_path(&mods_dir, &target_group)?.join(check_file_name(&target_name)?)
     };
 
     if dest_dir.exists() {
@@ -1020,11 +1156,14 @@ pub async fn install_mod_archive(
              
              if target_name.is_empty() { continue; }
 
-             let outpath = dest_dir.join(&target_name);
-             
-             // Basic zip slip check (simple version, assuming target_name is relative)
-             // Real world usage: crate `zip` recommends `enclosed_name`, but we modified the name.
-             // We trust our stripping logic doesn't add '..'
+             // Zip slip: entries with '..' or absolute names are skipped
+             let outpath = match resolve_under(&dest_dir, &target_name) {
+                 Ok(p) => p,
+                 Err(e) => {
+                     println!("[ModInstall] Skipping archive entry {}: {}", name, e);
+                     continue;
+                 }
+             };
              
             if name.ends_with('/') {
                 fs::create_dir_all(&outpath).map_err(|e| e.to_string())?;
@@ -1092,13 +1231,19 @@ pub async fn install_mod_archive(
              }
              if target_name.is_empty() { return Ok(true); }
              
-             let outpath = dest_dir.join(&target_name);
+             let outpath = match resolve_under(&dest_dir, &target_name) {
+                 Ok(p) => p,
+                 Err(e) => {
+                     println!("[ModInstall] Skipping archive entry {}: {}", name, e);
+                     return Ok(true);
+                 }
+             };
              
              if entry.is_directory() {
-                 fs::create_dir_all(&outpath).map_err(|e| sevenz_rust::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string()), std::borrow::Cow::Borrowed("create dir")))?;
+                 fs::create_dir_all(&outpath).map_err(|e| sevenz_rust::Error::Io(std::io::Error::other(e.to_string()), std::borrow::Cow::Borrowed("create dir")))?;
              } else {
                  if let Some(p) = outpath.parent() {
-                     fs::create_dir_all(p).map_err(|e| sevenz_rust::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, e.to_string()), std::borrow::Cow::Borrowed("create parent")))?;
+                     fs::create_dir_all(p).map_err(|e| sevenz_rust::Error::Io(std::io::Error::other(e.to_string()), std::borrow::Cow::Borrowed("create parent")))?;
                  }
                 // default_entry_extract_fn expects &PathBuf as third arg, it handles file creation internally usually?
                 // Actually let's check docs or use lower level copy
@@ -1142,11 +1287,7 @@ pub async fn install_mod_archive(
              root_items.push(entry.path());
          }
          
-         let should_strip = if root_items.len() == 1 && root_items[0].is_dir() {
-             true
-         } else {
-             false
-         };
+         let should_strip = root_items.len() == 1 && root_items[0].is_dir();
          
          if should_strip {
              let root_dir = &root_items[0];
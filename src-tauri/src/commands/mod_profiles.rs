@@ -0,0 +1,147 @@
+use crate::commands::mod_load_order::{split_disabled_prefix, strip_order_prefix};
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth, scan_folder, toggle_mod, ScanState};
+use crate::utils::file_manager::get_global_games_dir;
+use crate::utils::mod_paths::check_file_name;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+// Named sets of enabled mods, stored in Games/<game>/ModProfiles.json.
+// Mods are keyed by their path without DISABLED_ / load order prefixes, so a profile
+// still matches after toggling or re-prioritizing. Library links are left alone.
+
+const MOD_PROFILES_FILE: &str = "ModProfiles.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct ModProfilesFile {
+    #[serde(default)]
+    profiles: BTreeMap<String, Vec<String>>, // Profile name -> enabled mod keys
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModProfile {
+    pub name: String,
+    pub enabled_mods: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModProfileApplyResult {
+    pub enabled: Vec<String>,
+    pub disabled: Vec<String>,
+    pub missing: Vec<String>, // In the profile but no longer installed
+}
+
+fn profiles_path(app: &AppHandle, game_name: &str) -> PathBuf {
+    get_global_games_dir(app).join(game_name).join(MOD_PROFILES_FILE)
+}
+
+fn load_profiles(app: &AppHandle, game_name: &str) -> ModProfilesFile {
+    fs::read_to_string(profiles_path(app, game_name))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_profiles(app: &AppHandle, game_name: &str, profiles: &ModProfilesFile) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(profiles).map_err(|e| format!("Failed to serialize mod profiles: {}", e))?;
+    fs::write(profiles_path(app, game_name), content).map_err(|e| format!("Failed to write mod profiles: {}", e))
+}
+
+// "Char/DISABLED_#050 Foo" -> "char/foo"
+fn mod_key(relative_path: &str) -> String {
+    relative_path
+        .split(['/', '\\'])
+        .filter(|c| !c.is_empty())
+        .map(|c| split_disabled_prefix(strip_order_prefix(split_disabled_prefix(c).1)).1.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn scan_game_mods(app: &AppHandle, game_name: &str) -> Result<Vec<crate::commands::mod_manager::ModInfo>, String> {
+    let mods_dir = get_game_install_dir(app, game_name)?.join("Mods");
+    if !mods_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut state = ScanState::new(get_mod_scan_depth(app, game_name));
+    scan_folder(&mods_dir, &mods_dir, "Root".to_string(), 0, false, &mut state);
+    Ok(state.mods)
+}
+
+#[tauri::command]
+pub fn list_mod_profiles(app: AppHandle, game_name: String) -> Result<Vec<ModProfile>, String> {
+    Ok(load_profiles(&app, &game_name)
+        .profiles
+        .into_iter()
+        .map(|(name, enabled_mods)| ModProfile { name, enabled_mods })
+        .collect())
+}
+
+// Snapshot the currently enabled mods under a name, replacing a profile with the same name
+#[tauri::command]
+pub fn save_mod_profile(app: AppHandle, game_name: String, profile_name: String) -> Result<ModProfile, String> {
+    let name = check_file_name(profile_name.trim())?.to_string();
+    let mut enabled_mods: Vec<String> = scan_game_mods(&app, &game_name)?
+        .iter()
+        .filter(|m| m.enabled)
+        .map(|m| mod_key(&m.relative_path))
+        .collect();
+    enabled_mods.sort();
+
+    let mut profiles = load_profiles(&app, &game_name);
+    profiles.profiles.insert(name.clone(), enabled_mods.clone());
+    save_profiles(&app, &game_name, &profiles)?;
+    Ok(ModProfile { name, enabled_mods })
+}
+
+#[tauri::command]
+pub fn delete_mod_profile(app: AppHandle, game_name: String, profile_name: String) -> Result<(), String> {
+    let mut profiles = load_profiles(&app, &game_name);
+    if profiles.profiles.remove(&profile_name).is_none() {
+        return Err(format!("Mod profile not found: {}", profile_name));
+    }
+    save_profiles(&app, &game_name, &profiles)
+}
+
+// Enable exactly the mods in the profile and disable the rest
+#[tauri::command]
+pub async fn apply_mod_profile(app: AppHandle, game_name: String, profile_name: String) -> Result<ModProfileApplyResult, String> {
+    let profiles = load_profiles(&app, &game_name);
+    let wanted: HashSet<String> = profiles
+        .profiles
+        .get(&profile_name)
+        .ok_or_else(|| format!("Mod profile not found: {}", profile_name))?
+        .iter()
+        .cloned()
+        .collect();
+
+    let mut result = ModProfileApplyResult::default();
+    let mut installed = HashSet::new();
+    let deployments = crate::commands::mod_library::load_deployments(&app, &game_name);
+    for m in scan_game_mods(&app, &game_name)? {
+        let key = mod_key(&m.relative_path);
+        installed.insert(key.clone());
+        let enable = wanted.contains(&key);
+        if enable == m.enabled || crate::commands::mod_library::find_deployment_in(&deployments, &m.relative_path).is_some() {
+            continue;
+        }
+        toggle_mod(app.clone(), game_name.clone(), m.relative_path.clone(), enable).await?;
+        if enable {
+            result.enabled.push(m.relative_path);
+        } else {
+            result.disabled.push(m.relative_path);
+        }
+    }
+    result.missing = wanted.into_iter().filter(|k| !installed.contains(k)).collect();
+    result.missing.sort();
+
+    println!(
+        "[ModProfiles] Applied '{}' to {}: {} enabled, {} disabled, {} missing",
+        profile_name, game_name, result.enabled.len(), result.disabled.len(), result.missing.len()
+    );
+    Ok(result)
+}
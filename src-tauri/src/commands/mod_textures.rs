@@ -0,0 +1,180 @@
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth};
+use crate::configs::app_config::AppConfig;
+use crate::utils::dds::{read_dds_info, DdsInfo};
+use crate::utils::mod_ini::{collect_mod_inis, ModIni};
+use crate::utils::mod_paths::{resolve_mod_path, resolve_under};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+const PREVIEW_MAX_SIZE: u32 = 512;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TextureInfo {
+    pub resource: String, // Section name, e.g. "ResourceBodyDiffuse"
+    pub ini_path: String,
+    pub file: String,     // Absolute path of the .dds
+    pub size_bytes: u64,
+    pub dds: Option<DdsInfo>,
+    pub error: Option<String>, // Missing file or unreadable header
+}
+
+// Every [Resource*] of the mod whose filename points at a .dds file
+pub(crate) fn collect_mod_textures(mods_dir: &Path, mod_relative_path: &str, scan_depth: usize) -> Vec<TextureInfo> {
+    let wanted = mod_relative_path.replace('\\', "/");
+    let mut textures: Vec<TextureInfo> = Vec::new();
+
+    for ini_file in collect_mod_inis(mods_dir, scan_depth) {
+        if ini_file.mod_relative_path.replace('\\', "/").trim_matches('/') != wanted.trim_matches('/') {
+            continue;
+        }
+        let ini = match ModIni::load(&ini_file.ini_path) {
+            Ok(ini) => ini,
+            Err(_) => continue,
+        };
+        let ini_dir = ini_file.ini_path.parent().unwrap_or(mods_dir).to_path_buf();
+
+        for section in ini.sections_with_prefix("Resource") {
+            let filename = match section.get("filename") {
+                Some(f) => f.trim_matches('"').replace('\\', "/"),
+                None => continue,
+            };
+            if !filename.to_lowercase().ends_with(".dds") {
+                continue;
+            }
+
+            let path = ini_dir.join(&filename);
+            if textures.iter().any(|t| Path::new(&t.file) == path) {
+                continue;
+            }
+
+            let (size_bytes, dds, error) = match fs::metadata(&path) {
+                Ok(meta) => match read_dds_info(&path) {
+                    Ok(info) => (meta.len(), Some(info), None),
+                    Err(e) => (meta.len(), None, Some(e)),
+                },
+                Err(_) => (0, None, Some(format!("File not found: {}", filename))),
+            };
+
+            textures.push(TextureInfo {
+                resource: section.name.clone(),
+                ini_path: ini_file.ini_path.to_string_lossy().to_string(),
+                file: path.to_string_lossy().to_string(),
+                size_bytes,
+                dds,
+                error,
+            });
+        }
+    }
+    textures
+}
+
+// Best guess at the texture that shows what the mod looks like
+fn pick_diffuse(textures: &[TextureInfo]) -> Option<&TextureInfo> {
+    let readable = || textures.iter().filter(|t| t.dds.is_some());
+    let name_has = |t: &TextureInfo, words: &[&str]| {
+        let name = format!("{} {}", t.resource, t.file).to_lowercase();
+        words.iter().any(|w| name.contains(w))
+    };
+    let area = |t: &&TextureInfo| t.dds.as_ref().map(|d| d.width as u64 * d.height as u64).unwrap_or(0);
+
+    readable()
+        .filter(|t| name_has(t, &["diffuse", "albedo", "basecolor", "base_color"]))
+        .max_by_key(area)
+        .or_else(|| {
+            readable()
+                .filter(|t| !name_has(t, &["normal", "lightmap", "shadow", "mask", "ramp"]))
+                .max_by_key(area)
+        })
+        .or_else(|| readable().max_by_key(area))
+}
+
+fn preview_cache_path(texture: &Path) -> Result<PathBuf, String> {
+    let app_config = AppConfig::load().map_err(|e| format!("Failed to load app config: {}", e))?;
+    if app_config.cache_dir.is_empty() {
+        return Err("Cache Dir not set.".to_string());
+    }
+
+    // Key on path + size + mtime so replaced textures get a fresh preview
+    let meta = fs::metadata(texture).map_err(|e| format!("Failed to read texture: {}", e))?;
+    let mut hasher = DefaultHasher::new();
+    texture.to_string_lossy().to_lowercase().hash(&mut hasher);
+    meta.len().hash(&mut hasher);
+    if let Ok(modified) = meta.modified() {
+        modified.hash(&mut hasher);
+    }
+
+    let dir = PathBuf::from(&app_config.cache_dir).join("DdsPreviews");
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create preview cache: {}", e))?;
+    Ok(dir.join(format!("{:016x}.png", hasher.finish())))
+}
+
+fn decode_dds_to_png(src: &Path, dest: &Path) -> Result<(), String> {
+    let file = fs::File::open(src).map_err(|e| format!("Failed to open texture: {}", e))?;
+    let mut reader = std::io::BufReader::new(file);
+    let dds = ddsfile::Dds::read(&mut reader).map_err(|e| format!("Failed to read DDS: {}", e))?;
+    let image = image_dds::image_from_dds(&dds, 0).map_err(|e| format!("Failed to decode DDS: {}", e))?;
+
+    let (width, height) = image.dimensions();
+    let scale = (PREVIEW_MAX_SIZE as f32 / width.max(height) as f32).min(1.0);
+    let thumbnail = image::imageops::thumbnail(
+        &image,
+        ((width as f32 * scale) as u32).max(1),
+        ((height as f32 * scale) as u32).max(1),
+    );
+    thumbnail.save(dest).map_err(|e| format!("Failed to write preview: {}", e))
+}
+
+#[tauri::command]
+pub fn inspect_mod_textures(app: AppHandle, game_name: String, mod_relative_path: String) -> Result<Vec<TextureInfo>, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    Ok(collect_mod_textures(&mods_dir, &mod_relative_path, get_mod_scan_depth(&app, &game_name)))
+}
+
+// Decode a DDS (the given one, or the mod's diffuse texture) into a cached PNG thumbnail
+#[tauri::command]
+pub async fn generate_dds_preview(
+    app: AppHandle,
+    game_name: String,
+    mod_relative_path: String,
+    texture_path: Option<String>,
+) -> Result<String, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+
+    let source = match texture_path {
+        // TextureInfo.file is absolute, either way it has to stay inside the mod
+        Some(p) => {
+            let mod_dir = resolve_mod_path(&mods_dir, &mod_relative_path)?;
+            let relative = Path::new(&p)
+                .strip_prefix(&mod_dir)
+                .map(|r| r.to_string_lossy().to_string())
+                .unwrap_or(p);
+            resolve_under(&mod_dir, &relative)?
+        }
+        None => {
+            let textures = collect_mod_textures(&mods_dir, &mod_relative_path, get_mod_scan_depth(&app, &game_name));
+            let diffuse = pick_diffuse(&textures).ok_or("This mod has no readable DDS textures")?;
+            PathBuf::from(&diffuse.file)
+        }
+    };
+    if !source.is_file() {
+        return Err(format!("Texture not found: {:?}", source));
+    }
+
+    let dest = preview_cache_path(&source)?;
+    if dest.exists() {
+        return Ok(dest.to_string_lossy().to_string());
+    }
+
+    println!("[ModTextures] Decoding {:?} -> {:?}", source, dest);
+    let out = dest.clone();
+    tauri::async_runtime::spawn_blocking(move || decode_dds_to_png(&source, &out))
+        .await
+        .map_err(|e| format!("Preview task failed: {}", e))??;
+
+    Ok(dest.to_string_lossy().to_string())
+}
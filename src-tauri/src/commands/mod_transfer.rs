@@ -0,0 +1,368 @@
+use crate::commands::mod_library::{deploy_library_mod, find_deployment, get_library_dir, load_deployments, undeploy_library_mod, LibraryDeployment};
+use crate::commands::mod_load_order::transfer_priorities;
+use crate::commands::mod_manager::get_game_install_dir;
+use crate::utils::file_manager::copy_dir_recursive;
+use crate::utils::mod_paths::resolve_mod_path;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+// Copy or move mods/groups from one game config's Mods folder into another's.
+// Paths keep their group structure (and DISABLED_ / priority prefixes); ssmt4_mod.json
+// travels with the folder and the load order priorities are copied to the target game.
+// Library deployments are re-linked when both games share a library.
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum TransferMode {
+    Copy,
+    Move,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ClashPolicy {
+    Skip,    // Leave the existing folder alone
+    Rename,  // Transfer as "Name (2)"
+    Replace, // Remove the existing folder first
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum TransferStatus {
+    Transferred,
+    Renamed,
+    Replaced,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferResult {
+    pub source: String,         // Relative path in the source Mods
+    pub target: Option<String>, // Relative path in the target Mods
+    pub status: TransferStatus,
+    pub error: Option<String>,
+}
+
+fn relative_to(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base).unwrap_or(path).to_string_lossy().to_string()
+}
+
+// "Mods/Group/Name" -> "Mods/Group/Name (2)", first free one
+pub(crate) fn next_free_name(path: &Path) -> Result<PathBuf, String> {
+    let name = path.file_name().ok_or("Invalid path")?.to_string_lossy().to_string();
+    for i in 2..1000 {
+        let candidate = path.with_file_name(format!("{} ({})", name, i));
+        if fs::symlink_metadata(&candidate).is_err() {
+            return Ok(candidate);
+        }
+    }
+    Err(format!("No free name for {}", name))
+}
+
+pub(crate) fn remove_existing(app: &AppHandle, game_name: &str, mods_dir: &Path, path: &Path) -> Result<(), String> {
+    let relative = relative_to(mods_dir, path);
+    if let Some(deployment) = find_deployment(app, game_name, &relative) {
+        return undeploy_library_mod(app.clone(), game_name.to_string(), deployment.library_id);
+    }
+    // Links inside a replaced group go through the manifest too
+    for deployment in deployments_below(load_deployments(app, game_name), &relative) {
+        undeploy_library_mod(app.clone(), game_name.to_string(), deployment.library_id)?;
+    }
+    if path.is_dir() {
+        fs::remove_dir_all(path).map_err(|e| format!("Failed to remove existing {}: {}", relative, e))
+    } else {
+        fs::remove_file(path).map_err(|e| format!("Failed to remove existing {}: {}", relative, e))
+    }
+}
+
+pub(crate) fn copy_or_move(src: &Path, dest: &Path, mode: TransferMode) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create group folder: {}", e))?;
+    }
+    if mode == TransferMode::Move && fs::rename(src, dest).is_ok() {
+        return Ok(());
+    }
+
+    // Copy, or move across drives
+    if let Err(e) = copy_dir_recursive(src, dest) {
+        let _ = fs::remove_dir_all(dest);
+        return Err(format!("Failed to copy: {}", e));
+    }
+    if mode == TransferMode::Move {
+        fs::remove_dir_all(src).map_err(|e| format!("Copied but failed to remove the original: {}", e))?;
+    }
+    Ok(())
+}
+
+// Deployments whose link lives strictly inside the group at `relative`
+fn deployments_below(deployments: Vec<LibraryDeployment>, relative: &str) -> Vec<LibraryDeployment> {
+    let prefix = format!("{}/", relative.replace('\\', "/").trim_matches('/').to_lowercase());
+    deployments
+        .into_iter()
+        .filter(|d| d.target.replace('\\', "/").to_lowercase().starts_with(&prefix))
+        .collect()
+}
+
+// Where a link inside the group at `from_group` ends up once the group lands at `to_group`:
+// ("Ayaka/Outfits/ModB", "Ayaka", "Chars/Ayaka") -> "Chars/Ayaka/Outfits/ModB"
+fn relocate_target(target: &str, from_group: &str, to_group: &str) -> String {
+    let target = target.replace('\\', "/");
+    let from_group = from_group.replace('\\', "/");
+    let inner = target.trim_matches('/').get(from_group.trim_matches('/').len()..).unwrap_or("");
+    format!("{}/{}", to_group.replace('\\', "/").trim_matches('/'), inner.trim_matches('/'))
+}
+
+// Copy a folder following links, so library deployments arrive as real folders.
+// Folders in `skip` (links that get redeployed instead) are left out.
+fn copy_tree(src: &Path, dest: &Path, skip: &[PathBuf]) -> Result<(), String> {
+    fs::create_dir_all(dest).map_err(|e| format!("Failed to create {:?}: {}", dest, e))?;
+    for entry in fs::read_dir(src).map_err(|e| format!("Failed to read {:?}: {}", src, e))?.flatten() {
+        let path = entry.path();
+        if skip.contains(&path) {
+            continue;
+        }
+        let target = dest.join(entry.file_name());
+        if path.is_dir() {
+            copy_tree(&path, &target, skip)?;
+        } else {
+            fs::copy(&path, &target).map_err(|e| format!("Failed to copy {:?}: {}", path, e))?;
+        }
+    }
+    Ok(())
+}
+
+// A group holding library links: links are redeployed in the target when it shares the
+// library, otherwise copied as real folders. Either way both manifests stay in sync.
+#[allow(clippy::too_many_arguments)]
+fn transfer_group_with_links(
+    app: &AppHandle,
+    source_game: &str,
+    target_game: &str,
+    source_mods: &Path,
+    target_mods: &Path,
+    src: &Path,
+    dest: &Path,
+    links: Vec<LibraryDeployment>,
+    mode: TransferMode,
+    redeploy: bool,
+) -> Result<(), String> {
+    let link_paths: Vec<PathBuf> = links.iter().map(|d| source_mods.join(&d.target)).collect();
+    let skip: &[PathBuf] = if redeploy { &link_paths } else { &[] };
+    if let Err(e) = copy_tree(src, dest, skip) {
+        let _ = fs::remove_dir_all(dest);
+        return Err(e);
+    }
+    if mode == TransferMode::Move {
+        for d in &links {
+            undeploy_library_mod(app.clone(), source_game.to_string(), d.library_id.clone())?;
+        }
+        fs::remove_dir_all(src).map_err(|e| format!("Copied but failed to remove the original: {}", e))?;
+    }
+    if redeploy {
+        // Same place inside the transferred group as in the source
+        let source_group = relative_to(source_mods, src);
+        let dest_group = relative_to(target_mods, dest);
+        for d in links {
+            let target = relocate_target(&d.target, &source_group, &dest_group);
+            deploy_library_mod(app.clone(), target_game.to_string(), d.library_id, Some(d.mode), Some(target))?;
+        }
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn transfer_one(
+    app: &AppHandle,
+    source_game: &str,
+    target_game: &str,
+    source_mods: &Path,
+    target_mods: &Path,
+    relative: &str,
+    mode: TransferMode,
+    on_clash: ClashPolicy,
+) -> Result<TransferResult, String> {
+    let src = resolve_mod_path(source_mods, relative)?;
+    if !src.is_dir() {
+        return Err("Mod or group not found".to_string());
+    }
+    let mut dest = resolve_mod_path(target_mods, &relative_to(source_mods, &src))?;
+
+    let mut status = TransferStatus::Transferred;
+    if fs::symlink_metadata(&dest).is_ok() {
+        match on_clash {
+            ClashPolicy::Skip => {
+                return Ok(TransferResult {
+                    source: relative.to_string(),
+                    target: Some(relative_to(target_mods, &dest)),
+                    status: TransferStatus::Skipped,
+                    error: None,
+                });
+            }
+            ClashPolicy::Rename => {
+                dest = next_free_name(&dest)?;
+                status = TransferStatus::Renamed;
+            }
+            ClashPolicy::Replace => {
+                remove_existing(app, target_game, target_mods, &dest)?;
+                status = TransferStatus::Replaced;
+            }
+        }
+    }
+    let mut dest_relative = relative_to(target_mods, &dest);
+
+    // Library links stay links when the target game uses the same library
+    let deployment = find_deployment(app, source_game, relative);
+    let same_library = match (get_library_dir(app, source_game), get_library_dir(app, target_game)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    };
+
+    match deployment {
+        Some(d) if same_library && status != TransferStatus::Renamed => {
+            dest_relative = deploy_library_mod(app.clone(), target_game.to_string(), d.library_id.clone(), Some(d.mode), Some(dest_relative))?;
+            if mode == TransferMode::Move {
+                undeploy_library_mod(app.clone(), source_game.to_string(), d.library_id)?;
+            }
+        }
+        Some(d) => {
+            copy_or_move(&src, &dest, TransferMode::Copy)?;
+            if mode == TransferMode::Move {
+                undeploy_library_mod(app.clone(), source_game.to_string(), d.library_id)?;
+            }
+        }
+        None => {
+            let links = deployments_below(load_deployments(app, source_game), relative);
+            if links.is_empty() {
+                copy_or_move(&src, &dest, mode)?;
+            } else {
+                let redeploy = same_library && status != TransferStatus::Renamed;
+                transfer_group_with_links(app, source_game, target_game, source_mods, target_mods, &src, &dest, links, mode, redeploy)?;
+            }
+        }
+    }
+
+    transfer_priorities(app, source_game, target_game, relative, &dest_relative, mode == TransferMode::Move)?;
+
+    println!("[ModTransfer] {:?} {}:{} -> {}:{}", mode, source_game, relative, target_game, dest_relative);
+    Ok(TransferResult {
+        source: relative.to_string(),
+        target: Some(dest_relative),
+        status,
+        error: None,
+    })
+}
+
+#[tauri::command]
+pub async fn transfer_mods(
+    app: AppHandle,
+    source_game: String,
+    target_game: String,
+    paths: Vec<String>, // Mod or group relative paths in the source Mods
+    mode: TransferMode,
+    on_clash: Option<ClashPolicy>,
+) -> Result<Vec<TransferResult>, String> {
+    if source_game == target_game {
+        return Err("Source and target game are the same, use move_mod_to_group instead".to_string());
+    }
+    let on_clash = on_clash.unwrap_or(ClashPolicy::Rename);
+
+    let source_mods = get_game_install_dir(&app, &source_game)?.join("Mods");
+    let target_mods = get_game_install_dir(&app, &target_game)?.join("Mods");
+    if source_mods == target_mods {
+        return Err("Both games use the same Mods folder".to_string());
+    }
+    fs::create_dir_all(&target_mods).map_err(|e| format!("Failed to create target Mods directory: {}", e))?;
+
+    // A mod inside a selected group goes along with the group
+    let normalized: Vec<String> = paths.iter().map(|p| p.replace('\\', "/").trim_matches('/').to_string()).collect();
+    let selected: Vec<&String> = normalized
+        .iter()
+        .filter(|p| !normalized.iter().any(|other| other != *p && p.starts_with(&format!("{}/", other))))
+        .collect();
+
+    let mut results = Vec::new();
+    for relative in selected {
+        let result = transfer_one(&app, &source_game, &target_game, &source_mods, &target_mods, relative, mode, on_clash)
+            .unwrap_or_else(|e| TransferResult {
+                source: relative.clone(),
+                target: None,
+                status: TransferStatus::Failed,
+                error: Some(e),
+            });
+        results.push(result);
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::mod_library::DeployMode;
+    use crate::utils::test_support::TempDir;
+
+    fn deployment(target: &str) -> LibraryDeployment {
+        LibraryDeployment { library_id: target.to_string(), target: target.to_string(), mode: DeployMode::Symlink }
+    }
+
+    #[test]
+    fn finds_links_inside_a_group_only() {
+        let list = vec![deployment("Ayaka/ModA"), deployment("Ayaka/Outfits/ModB"), deployment("AyakaAlt/ModC"), deployment("Ayaka")];
+        let found: Vec<String> = deployments_below(list, r"ayaka\").into_iter().map(|d| d.target).collect();
+        assert_eq!(found, vec!["Ayaka/ModA", "Ayaka/Outfits/ModB"]);
+    }
+
+    #[test]
+    fn relocates_links_into_the_transferred_group() {
+        assert_eq!(relocate_target("Ayaka/Outfits/ModB", "Ayaka", "Ayaka"), "Ayaka/Outfits/ModB");
+        assert_eq!(relocate_target(r"Chars\Ayaka\ModA", "Chars/Ayaka", "Chars/Ayaka (2)"), "Chars/Ayaka (2)/ModA");
+    }
+
+    // A linked mod inside a transferred group: the group is copied without the link, and the
+    // link's new place is inside the copied group rather than at the Mods root
+    #[cfg(unix)]
+    #[test]
+    fn linked_mod_inside_a_group_lands_inside_the_copy() {
+        let dir = TempDir::new("transfer_group_link");
+        dir.write("Library/ModA/mod.ini", "a");
+        dir.write("Source/Chars/Ayaka/Own/own.ini", "own");
+        let link = dir.path().join("Source/Chars/Ayaka/Outfits/ModA");
+        fs::create_dir_all(link.parent().unwrap()).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("Library/ModA"), &link).unwrap();
+
+        let source_mods = dir.path().join("Source");
+        let target_mods = dir.path().join("Target");
+        let links = deployments_below(
+            vec![LibraryDeployment { library_id: "ModA".to_string(), target: "Chars/Ayaka/Outfits/ModA".to_string(), mode: DeployMode::Symlink }],
+            "Chars/Ayaka",
+        );
+        assert_eq!(links.len(), 1);
+
+        let src = source_mods.join("Chars/Ayaka");
+        let dest = target_mods.join("Chars/Ayaka");
+        copy_tree(&src, &dest, &[source_mods.join(&links[0].target)]).unwrap();
+        assert!(dest.join("Own/own.ini").is_file());
+        assert!(!dest.join("Outfits/ModA").exists());
+
+        let target = relocate_target(&links[0].target, &relative_to(&source_mods, &src), &relative_to(&target_mods, &dest));
+        assert_eq!(target, "Chars/Ayaka/Outfits/ModA");
+        assert!(resolve_mod_path(&target_mods, &target).unwrap().starts_with(&dest));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copies_links_as_folders_or_skips_them() {
+        let dir = TempDir::new("transfer_copy_tree");
+        dir.write("Library/ModA/mod.ini", "a");
+        dir.write("Mods/Group/Own/own.ini", "own");
+        std::os::unix::fs::symlink(dir.path().join("Library/ModA"), dir.path().join("Mods/Group/ModA")).unwrap();
+
+        copy_tree(&dir.path().join("Mods/Group"), &dir.path().join("Full"), &[]).unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("Full/ModA/mod.ini")).unwrap(), "a");
+        assert!(!fs::symlink_metadata(dir.path().join("Full/ModA")).unwrap().file_type().is_symlink());
+
+        copy_tree(&dir.path().join("Mods/Group"), &dir.path().join("Partial"), &[dir.path().join("Mods/Group/ModA")]).unwrap();
+        assert!(dir.path().join("Partial/Own/own.ini").is_file());
+        assert!(!dir.path().join("Partial/ModA").exists());
+    }
+}
@@ -0,0 +1,301 @@
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth, scan_folder, ScanState};
+use crate::configs::app_config::AppConfig;
+use crate::utils::mod_metadata::{load_mod_metadata, save_mod_metadata};
+use crate::utils::mod_paths::resolve_mod_path;
+use crate::utils::mod_source::{GameBananaClient, ModSourceClient, RemoteChangelog, RemoteModFile};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+
+const DEFAULT_CHECK_INTERVAL_MINUTES: u64 = 360;
+
+// Periodic checker state, like ModWatcher
+pub struct ModUpdateChecker(pub Mutex<Option<tauri::async_runtime::JoinHandle<()>>>);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModUpdateInfo {
+    pub mod_relative_path: String,
+    pub name: String,
+    pub source_url: String,
+    pub installed_version: Option<String>,
+    pub installed_at: u64, // From metadata, or the folder's modified time
+    pub latest_version: Option<String>,
+    pub latest_updated_at: u64,
+    pub has_update: bool,
+    pub changelog: Vec<RemoteChangelog>, // Only entries newer than the installed files
+    pub files: Vec<RemoteModFile>,
+    pub error: Option<String>,
+}
+
+pub(crate) async fn check_updates_with<C: ModSourceClient>(client: &C, mods_dir: &Path, scan_depth: usize) -> Vec<ModUpdateInfo> {
+    let mut state = ScanState::new(scan_depth);
+    scan_folder(mods_dir, mods_dir, "Root".to_string(), 0, false, &mut state);
+
+    let mut results = Vec::new();
+    for m in state.mods {
+        let metadata = load_mod_metadata(Path::new(&m.path));
+        let source_url = match metadata.source_url {
+            Some(url) => url,
+            None => continue,
+        };
+        let installed_at = metadata.installed_at.unwrap_or(m.last_modified);
+        let mut info = ModUpdateInfo {
+            mod_relative_path: m.relative_path.clone(),
+            name: m.name.clone(),
+            source_url: source_url.clone(),
+            installed_version: metadata.installed_version.clone(),
+            installed_at,
+            latest_version: None,
+            latest_updated_at: 0,
+            has_update: false,
+            changelog: Vec::new(),
+            files: Vec::new(),
+            error: None,
+        };
+
+        let id = match client.parse_source(&source_url) {
+            Some(id) => id,
+            None => {
+                info.error = Some("Unsupported source url".to_string());
+                results.push(info);
+                continue;
+            }
+        };
+
+        match client.fetch_mod(&id).await {
+            Ok(remote) => {
+                let newest_file = remote.files.iter().map(|f| f.added_at).max().unwrap_or(0);
+                info.has_update = match (&metadata.installed_version, &remote.version) {
+                    (Some(installed), Some(latest)) => installed != latest,
+                    // Without versions only new uploads count, page edits and folder touches do not
+                    _ => newest_file > installed_at,
+                };
+                info.latest_version = remote.version;
+                info.latest_updated_at = remote.updated_at;
+                info.files = remote.files;
+            }
+            Err(e) => info.error = Some(e),
+        }
+
+        if info.has_update {
+            match client.fetch_changelog(&id).await {
+                Ok(log) => info.changelog = log.into_iter().filter(|c| c.date > installed_at).collect(),
+                Err(e) => println!("[ModUpdates] Changelog for {} failed: {}", id, e),
+            }
+        }
+        results.push(info);
+    }
+    results
+}
+
+fn source_client() -> GameBananaClient {
+    let base_url = AppConfig::load().map(|c| c.mod_source_api_url).unwrap_or_default();
+    GameBananaClient::new(&base_url)
+}
+
+#[tauri::command]
+pub async fn check_mod_updates(app: AppHandle, game_name: String) -> Result<Vec<ModUpdateInfo>, String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    if !mods_dir.exists() {
+        return Err("Mods directory not found".to_string());
+    }
+    let scan_depth = get_mod_scan_depth(&app, &game_name);
+    Ok(check_updates_with(&source_client(), &mods_dir, scan_depth).await)
+}
+
+// Link a mod to its download page. installed_at defaults to now when a version is given.
+#[tauri::command]
+pub fn set_mod_source(
+    app: AppHandle,
+    game_name: String,
+    mod_relative_path: String,
+    source_url: Option<String>,
+    installed_version: Option<String>,
+) -> Result<(), String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    let mod_dir = resolve_mod_path(&mods_dir, &mod_relative_path)?;
+    if !mod_dir.is_dir() {
+        return Err("Mod directory not found".to_string());
+    }
+
+    let mut metadata = load_mod_metadata(&mod_dir);
+    metadata.source_url = source_url.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    if installed_version.is_some() {
+        metadata.installed_version = installed_version;
+        metadata.installed_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_secs());
+    }
+    save_mod_metadata(&mod_dir, &metadata)
+}
+
+// Re-check every interval and emit "mod-updates-available" with the mods that have updates
+#[tauri::command]
+pub fn start_mod_update_checker(
+    app: AppHandle,
+    state: State<'_, ModUpdateChecker>,
+    game_name: String,
+    interval_minutes: Option<u64>,
+) -> Result<(), String> {
+    let mods_dir = get_game_install_dir(&app, &game_name)?.join("Mods");
+    let scan_depth = get_mod_scan_depth(&app, &game_name);
+    let interval = std::time::Duration::from_secs(interval_minutes.unwrap_or(DEFAULT_CHECK_INTERVAL_MINUTES).max(5) * 60);
+
+    let mut guard = state.0.lock().unwrap();
+    if let Some(handle) = guard.take() {
+        handle.abort();
+    }
+
+    let app_handle = app.clone();
+    let handle = tauri::async_runtime::spawn(async move {
+        loop {
+            let updates: Vec<ModUpdateInfo> = check_updates_with(&source_client(), &mods_dir, scan_depth)
+                .await
+                .into_iter()
+                .filter(|u| u.has_update)
+                .collect();
+            println!("[ModUpdates] {} mod(s) with updates for {}", updates.len(), game_name);
+            if !updates.is_empty() {
+                let _ = app_handle.emit("mod-updates-available", &updates);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    });
+    *guard = Some(handle);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_mod_update_checker(state: State<'_, ModUpdateChecker>) -> Result<(), String> {
+    if let Some(handle) = state.0.lock().unwrap().take() {
+        handle.abort();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::mod_metadata::ModMetadata;
+    use crate::utils::mod_source::RemoteModInfo;
+    use crate::utils::test_support::TempDir;
+
+    struct MockClient {
+        remote: RemoteModInfo,
+        changelog: Vec<RemoteChangelog>,
+    }
+
+    impl ModSourceClient for MockClient {
+        fn parse_source(&self, source_url: &str) -> Option<String> {
+            source_url.strip_prefix("mock:").map(|s| s.to_string())
+        }
+
+        async fn fetch_mod(&self, _id: &str) -> Result<RemoteModInfo, String> {
+            Ok(self.remote.clone())
+        }
+
+        async fn fetch_changelog(&self, _id: &str) -> Result<Vec<RemoteChangelog>, String> {
+            Ok(self.changelog.clone())
+        }
+    }
+
+    fn remote(version: Option<&str>, page_updated: u64, file_times: &[u64]) -> RemoteModInfo {
+        RemoteModInfo {
+            id: "1".to_string(),
+            name: "Mod".to_string(),
+            version: version.map(|v| v.to_string()),
+            updated_at: page_updated,
+            page_url: String::new(),
+            files: file_times
+                .iter()
+                .map(|t| RemoteModFile {
+                    id: t.to_string(),
+                    name: "mod.zip".to_string(),
+                    size: 0,
+                    download_url: String::new(),
+                    added_at: *t,
+                    version: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn changelog(date: u64) -> RemoteChangelog {
+        RemoteChangelog { title: format!("update {}", date), version: None, date, entries: Vec::new() }
+    }
+
+    fn install_mod(dir: &TempDir, name: &str, source: &str, version: Option<&str>, installed_at: u64) {
+        let mod_dir = dir.path().join(name);
+        dir.write(&format!("{}/mod.ini", name), "[TextureOverrideA]\nhash = 1\n");
+        let metadata = ModMetadata {
+            source_url: Some(source.to_string()),
+            installed_version: version.map(|v| v.to_string()),
+            installed_at: Some(installed_at),
+        };
+        save_mod_metadata(&mod_dir, &metadata).unwrap();
+    }
+
+    async fn check(dir: &TempDir, client: &MockClient) -> Vec<ModUpdateInfo> {
+        check_updates_with(client, dir.path(), 3).await
+    }
+
+    #[tokio::test]
+    async fn version_change_is_an_update() {
+        let dir = TempDir::new("updates_version");
+        install_mod(&dir, "A", "mock:1", Some("1.0"), 1000);
+        let client = MockClient { remote: remote(Some("1.1"), 500, &[500]), changelog: vec![changelog(900), changelog(1100)] };
+
+        let results = check(&dir, &client).await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].has_update);
+        assert_eq!(results[0].latest_version.as_deref(), Some("1.1"));
+        // Only changelog entries newer than the installed files
+        assert_eq!(results[0].changelog.len(), 1);
+        assert_eq!(results[0].changelog[0].date, 1100);
+    }
+
+    #[tokio::test]
+    async fn same_version_is_not_an_update() {
+        let dir = TempDir::new("updates_same");
+        install_mod(&dir, "A", "mock:1", Some("1.0"), 1000);
+        let client = MockClient { remote: remote(Some("1.0"), 5000, &[5000]), changelog: vec![changelog(5000)] };
+
+        let results = check(&dir, &client).await;
+        assert!(!results[0].has_update);
+        assert!(results[0].changelog.is_empty());
+    }
+
+    #[tokio::test]
+    async fn page_edit_without_new_files_is_not_an_update() {
+        let dir = TempDir::new("updates_page_edit");
+        install_mod(&dir, "A", "mock:1", None, 1000);
+        let client = MockClient { remote: remote(None, 9000, &[800]), changelog: Vec::new() };
+
+        assert!(!check(&dir, &client).await[0].has_update);
+    }
+
+    #[tokio::test]
+    async fn new_upload_without_versions_is_an_update() {
+        let dir = TempDir::new("updates_new_file");
+        install_mod(&dir, "A", "mock:1", None, 1000);
+        let client = MockClient { remote: remote(None, 1500, &[800, 1500]), changelog: vec![changelog(1500)] };
+
+        let results = check(&dir, &client).await;
+        assert!(results[0].has_update);
+        assert_eq!(results[0].changelog.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn unsupported_source_reports_an_error() {
+        let dir = TempDir::new("updates_unsupported");
+        install_mod(&dir, "A", "https://example.com/mod", None, 1000);
+        let client = MockClient { remote: remote(None, 0, &[]), changelog: Vec::new() };
+
+        let results = check(&dir, &client).await;
+        assert!(!results[0].has_update);
+        assert_eq!(results[0].error.as_deref(), Some("Unsupported source url"));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::commands::mod_manager::{get_game_install_dir, get_mod_scan_depth};
+use crate::utils::ini_manager::IniManager;
+use crate::utils::mod_ini::{collect_mod_inis, normalize_namespace, parse_variable_decl, ModIni};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+// 3DMigoto saves every `persist` variable into d3dx_user.ini as
+//   [Constants]
+//   $\mods\ayaka\ayaka.ini\swapvar = 1
+// i.e. "$\" + namespace + "\" + variable. Removing a line makes the mod fall back to
+// the default declared in its own ini on the next load.
+// Note: 3DMigoto rewrites this file when the game saves its state, so edits should be
+// made while the game is closed.
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserIniVariable {
+    pub name: String,                  // Variable name without '$'
+    pub value: Option<String>,         // Persisted value, None = not saved yet (default in use)
+    pub default_value: Option<String>, // Value declared in the mod ini, None if the mod is unknown
+    pub line: Option<usize>,           // Line in d3dx_user.ini
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModUserVariables {
+    pub namespace: String,
+    pub mod_relative_path: Option<String>, // None when no installed mod declares this namespace
+    pub ini_path: Option<String>,
+    pub enabled: bool,
+    pub variables: Vec<UserIniVariable>,
+}
+
+struct UserIniValue {
+    namespace: String,
+    name: String,
+    value: String,
+    line: usize,
+}
+
+fn read_user_ini(path: &Path) -> Vec<UserIniValue> {
+    let ini = match ModIni::load(path) {
+        Ok(ini) => ini,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut values = Vec::new();
+    for section in ini.sections.iter().filter(|s| s.name.eq_ignore_ascii_case("Constants")) {
+        for entry in &section.entries {
+            // "$\namespace\parts\var" -> ("namespace\parts", "var")
+            let Some(path) = entry.key.strip_prefix("$\\") else { continue };
+            let Some((namespace, name)) = path.rsplit_once('\\') else { continue };
+            values.push(UserIniValue {
+                namespace: normalize_namespace(namespace),
+                name: name.to_string(),
+                value: entry.value.clone(),
+                line: entry.line,
+            });
+        }
+    }
+    values
+}
+
+fn user_ini_key(namespace: &str, name: &str) -> String {
+    format!("$\\{}\\{}", normalize_namespace(namespace), name.trim_start_matches('$'))
+}
+
+#[tauri::command]
+pub fn get_mod_user_variables(app: AppHandle, game_name: String) -> Result<Vec<ModUserVariables>, String> {
+    let install_dir = get_game_install_dir(&app, &game_name)?;
+    let mods_dir = install_dir.join("Mods");
+
+    let mut stored = read_user_ini(&install_dir.join("d3dx_user.ini"));
+    let mut results = Vec::new();
+
+    for ini_file in collect_mod_inis(&mods_dir, get_mod_scan_depth(&app, &game_name)) {
+        let ini = match ModIni::load(&ini_file.ini_path) {
+            Ok(ini) => ini,
+            Err(e) => {
+                println!("[UserVars] {}", e);
+                continue;
+            }
+        };
+        let namespace = ini.effective_namespace(&install_dir);
+
+        let mut variables = Vec::new();
+        for section in ini.sections.iter().filter(|s| s.name.eq_ignore_ascii_case("Constants")) {
+            for decl in section.entries.iter().filter_map(parse_variable_decl) {
+                if !decl.persist {
+                    continue;
+                }
+                let saved = stored
+                    .iter()
+                    .position(|v| v.namespace == namespace && v.name.eq_ignore_ascii_case(&decl.name))
+                    .map(|i| stored.remove(i));
+                variables.push(UserIniVariable {
+                    name: decl.name,
+                    value: saved.as_ref().map(|v| v.value.clone()),
+                    default_value: Some(decl.default_value),
+                    line: saved.map(|v| v.line),
+                });
+            }
+        }
+
+        if !variables.is_empty() {
+            results.push(ModUserVariables {
+                namespace,
+                mod_relative_path: Some(ini_file.mod_relative_path),
+                ini_path: Some(ini_file.ini_path.to_string_lossy().to_string()),
+                enabled: ini_file.enabled,
+                variables,
+            });
+        }
+    }
+
+    // Whatever is left belongs to mods that were removed or renamed
+    for value in stored {
+        let variable = UserIniVariable {
+            name: value.name,
+            value: Some(value.value),
+            default_value: None,
+            line: Some(value.line),
+        };
+        match results.iter_mut().find(|r| r.mod_relative_path.is_none() && r.namespace == value.namespace) {
+            Some(orphan) => orphan.variables.push(variable),
+            None => results.push(ModUserVariables {
+                namespace: value.namespace,
+                mod_relative_path: None,
+                ini_path: None,
+                enabled: false,
+                variables: vec![variable],
+            }),
+        }
+    }
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub fn set_mod_user_variable(
+    app: AppHandle,
+    game_name: String,
+    namespace: String,
+    name: String,
+    value: String,
+) -> Result<(), String> {
+    // 3DMigoto variables are always floats
+    let value = value.trim().to_string();
+    if value.parse::<f64>().is_err() {
+        return Err(format!("Invalid value '{}': 3DMigoto variables must be numbers", value));
+    }
+
+    let install_dir = get_game_install_dir(&app, &game_name)?;
+    let user_ini_path = install_dir.join("d3dx_user.ini");
+    if !user_ini_path.exists() {
+        fs::write(&user_ini_path, "[Constants]\n")
+            .map_err(|e| format!("Failed to create d3dx_user.ini: {}", e))?;
+    }
+
+    let mut ini = IniManager::load(&user_ini_path)?;
+    ini.set("Constants", &user_ini_key(&namespace, &name), &value);
+    ini.save()
+}
+
+// Reset one variable, or every variable of the namespace when `name` is None
+#[tauri::command]
+pub fn reset_mod_user_variables(
+    app: AppHandle,
+    game_name: String,
+    namespace: String,
+    name: Option<String>,
+) -> Result<usize, String> {
+    let install_dir = get_game_install_dir(&app, &game_name)?;
+    let user_ini_path = install_dir.join("d3dx_user.ini");
+    if !user_ini_path.exists() {
+        return Ok(0);
+    }
+
+    let namespace = normalize_namespace(&namespace);
+    let targets: Vec<String> = read_user_ini(&user_ini_path)
+        .into_iter()
+        .filter(|v| v.namespace == namespace)
+        .filter(|v| name.as_ref().map(|n| v.name.eq_ignore_ascii_case(n.trim_start_matches('$'))).unwrap_or(true))
+        .map(|v| user_ini_key(&v.namespace, &v.name))
+        .collect();
+
+    if targets.is_empty() {
+        return Ok(0);
+    }
+
+    let mut ini = IniManager::load(&user_ini_path)?;
+    for key in &targets {
+        ini.remove_key("Constants", key);
+    }
+    ini.save()?;
+
+    println!("[UserVars] Reset {} value(s) in namespace {}", targets.len(), namespace);
+    Ok(targets.len())
+}
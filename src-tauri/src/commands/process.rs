@@ -1,8 +1,5 @@
 use tauri::{AppHandle, Manager, Runtime};
-use std::process::Command;
-use std::os::windows::process::CommandExt; // 仅限 Windows 平台，用于隐藏控制台窗口
-
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+use crate::utils::platform::hidden_command;
 
 #[tauri::command]
 pub fn run_resource_executable<R: Runtime>(
@@ -25,9 +22,8 @@ pub fn run_resource_executable<R: Runtime>(
     }
 
     // 3. 执行命令
-    let output = Command::new(&exe_path)
+    let output = hidden_command(&exe_path) // 默默执行，不跳出黑框
         .args(args)
-        .creation_flags(CREATE_NO_WINDOW) // 默默执行，不跳出黑框
         .output()
         .map_err(|e| format!("执行失败: {}", e))?;
 
@@ -21,6 +21,9 @@ pub struct AppConfig {
     pub window_height: f64,
     #[serde(default)]
     pub github_token: String,
+    // Mod update checks, empty = https://gamebanana.com/apiv11
+    #[serde(default)]
+    pub mod_source_api_url: String,
     
     // Page Visibility Settings
     #[serde(default)]
@@ -45,6 +48,7 @@ impl Default for AppConfig {
             window_width: 1280.0,
             window_height: 720.0,
             github_token: "".to_string(),            
+            mod_source_api_url: "".to_string(),
             show_mods: true,            
             show_websites: true,
             show_documents: true,
@@ -3,6 +3,7 @@ pub mod utils;
 mod commands; // 引入统一的命令模块
 
 use crate::configs::app_config::AppConfig;
+use std::collections::HashMap;
 use std::sync::Mutex;
 use tauri::{Manager, WindowEvent};
 
@@ -14,8 +15,17 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            let _ = app.get_webview_window("main").expect("no main window").set_focus();
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            // 快捷方式转发的 --launch 只启动游戏，不打扰当前界面
+            if let Some(launch) = commands::headless::parse_launch_args(&args) {
+                commands::headless::run_headless_launch(app, launch, false);
+                return;
+            }
+            let main_window = app.get_webview_window("main").expect("no main window");
+            let _ = main_window.show();
+            let _ = main_window.set_focus();
+            // 第二个实例带来的 ssmt4:// 链接
+            commands::deep_link::handle_deep_link_args(app, &args);
         }))
         .setup(|app| {
             let main_window = app.get_webview_window("main").unwrap();
@@ -35,6 +45,29 @@ pub fn run() {
             // 4. 初始化 ModWatcher 状态
             app.manage(commands::mod_manager::ModWatcher(Mutex::new(None)));
 
+            // 5. 初始化 Mod 更新检查状态
+            app.manage(commands::mod_updates::ModUpdateChecker(Mutex::new(None)));
+
+            // 6. 初始化游戏会话（游玩时长）跟踪状态
+            app.manage(commands::game_sessions::GameSessionTracker(Mutex::new(HashMap::new())));
+
+            // 7. 初始化 3DMigoto 日志跟踪状态
+            app.manage(commands::migoto_logs::MigotoLogTailer(Mutex::new(HashMap::new())));
+
+            // 8. 注册 ssmt4:// 协议，并处理启动参数中的链接
+            app.manage(commands::deep_link::PendingDeepLinks(Mutex::new(Vec::new())));
+            commands::deep_link::register_url_scheme();
+            let args: Vec<String> = std::env::args().collect();
+            commands::deep_link::handle_deep_link_args(app.handle(), &args);
+
+            // 9. 带 --launch 时无界面启动游戏，否则显示主窗口（配置中默认隐藏）
+            match commands::headless::parse_launch_args(&args) {
+                Some(launch) => commands::headless::run_headless_launch(app.handle(), launch, true),
+                None => {
+                    let _ = main_window.show();
+                }
+            }
+
             Ok(())
         })
         .on_window_event(|window, event| {
@@ -97,7 +130,45 @@ pub fn run() {
             commands::mod_manager::add_mod_preview_images,
             commands::mod_manager::move_mod_to_group,
             commands::mod_manager::delete_mod_group,
-            commands::mod_manager::delete_mod
+            commands::mod_manager::delete_mod,
+            commands::mod_library::scan_mod_library,
+            commands::mod_library::deploy_library_mod,
+            commands::mod_library::undeploy_library_mod,
+            commands::mod_library::move_mod_to_library,
+            commands::mod_user_vars::get_mod_user_variables,
+            commands::mod_user_vars::set_mod_user_variable,
+            commands::mod_user_vars::reset_mod_user_variables,
+            commands::mod_dependencies::analyze_mod_dependencies,
+            commands::mod_linter::lint_mod,
+            commands::mod_linter::lint_all_mods,
+            commands::mod_textures::inspect_mod_textures,
+            commands::mod_textures::generate_dds_preview,
+            commands::mod_buffers::validate_mod_buffers,
+            commands::mod_buffers::validate_all_mod_buffers,
+            commands::mod_footprint::get_mod_footprint,
+            commands::mod_load_order::set_mod_priority,
+            commands::mod_load_order::apply_mod_load_order,
+            commands::mod_load_order::detect_mod_conflicts,
+            commands::mod_manager::list_mod_variants,
+            commands::mod_manager::toggle_mod_variant,
+            commands::mod_transfer::transfer_mods,
+            commands::mod_importer::import_mods,
+            commands::mod_updates::check_mod_updates,
+            commands::mod_updates::set_mod_source,
+            commands::mod_updates::start_mod_update_checker,
+            commands::mod_updates::stop_mod_update_checker,
+            commands::downloads::download_to_cache,
+            commands::downloads::download_and_install_mod,
+            commands::deep_link::take_pending_deep_links,
+            commands::game_sessions::get_play_stats,
+            commands::migoto_logs::get_3dmigoto_diagnostics,
+            commands::game_diagnostics::diagnose_game,
+            commands::game_launcher::list_launch_profiles,
+            commands::mod_profiles::list_mod_profiles,
+            commands::mod_profiles::save_mod_profile,
+            commands::mod_profiles::delete_mod_profile,
+            commands::mod_profiles::apply_mod_profile,
+            commands::headless::create_game_shortcut
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
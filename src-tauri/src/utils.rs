@@ -1,2 +1,11 @@
 pub mod file_manager;
-pub mod ini_manager;
\ No newline at end of file
+pub mod ini_manager;
+pub mod mod_ini;
+pub mod dds;
+pub mod mod_metadata;
+pub mod mod_paths;
+pub mod mod_source;
+pub mod downloader;
+pub mod platform;
+#[cfg(test)]
+pub mod test_support;
@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+// Header-only DDS reader. Reads at most 148 bytes (magic + header + DX10 header),
+// so it is cheap enough to run over every texture of every mod.
+
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+const HEADER_LEN: usize = 4 + 124;
+const DX10_HEADER_LEN: usize = 20;
+
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDPF_LUMINANCE: u32 = 0x20000;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DdsInfo {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub mip_count: u32,
+    pub array_size: u32,
+    pub is_cubemap: bool,
+    pub format: String,              // DXGI style name, e.g. "BC7_UNORM_SRGB"
+    pub compression: Option<String>, // "BC1".."BC7" for block compressed formats
+    pub bits_per_pixel: u32,         // 0 when the format is not recognised
+}
+
+impl DdsInfo {
+    // Estimated GPU memory: every mip of every array layer (6 faces for cubemaps).
+    // Header values come from arbitrary files, sizes that do not fit in u64 are an error.
+    pub fn gpu_size_bytes(&self) -> Result<u64, String> {
+        let overflow = || format!("DDS size overflows ({}x{}x{}, {} layers)", self.width, self.height, self.depth, self.array_size);
+        let block_bytes: u64 = match self.compression.as_deref() {
+            Some("BC1") | Some("BC4") => 8,
+            Some(_) => 16,
+            None => 0,
+        };
+
+        let mut total = 0u64;
+        for level in 0..self.mip_count.min(16) {
+            let width = (self.width >> level).max(1) as u64;
+            let height = (self.height >> level).max(1) as u64;
+            let depth = (self.depth >> level).max(1) as u64;
+            let surface = if block_bytes > 0 {
+                width.div_ceil(4).checked_mul(height.div_ceil(4)).and_then(|n| n.checked_mul(block_bytes))
+            } else {
+                width
+                    .checked_mul(height)
+                    .and_then(|n| n.checked_mul(self.bits_per_pixel as u64))
+                    .map(|bits| bits.div_ceil(8))
+            };
+            total = surface
+                .and_then(|s| s.checked_mul(depth))
+                .and_then(|s| total.checked_add(s))
+                .ok_or_else(overflow)?;
+        }
+
+        let faces = if self.is_cubemap { 6 } else { 1 };
+        total
+            .checked_mul(self.array_size as u64)
+            .and_then(|t| t.checked_mul(faces))
+            .ok_or_else(overflow)
+    }
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([buf[offset], buf[offset + 1], buf[offset + 2], buf[offset + 3]])
+}
+
+// (name, compression, bits per pixel)
+fn dxgi_format(code: u32) -> (&'static str, Option<&'static str>, u32) {
+    match code {
+        2 => ("R32G32B32A32_FLOAT", None, 128),
+        10 => ("R16G16B16A16_FLOAT", None, 64),
+        11 => ("R16G16B16A16_UNORM", None, 64),
+        24 => ("R10G10B10A2_UNORM", None, 32),
+        28 => ("R8G8B8A8_UNORM", None, 32),
+        29 => ("R8G8B8A8_UNORM_SRGB", None, 32),
+        34 => ("R16G16_FLOAT", None, 32),
+        41 => ("R32_FLOAT", None, 32),
+        49 => ("R8G8_UNORM", None, 16),
+        54 => ("R16_FLOAT", None, 16),
+        56 => ("R16_UNORM", None, 16),
+        61 => ("R8_UNORM", None, 8),
+        65 => ("A8_UNORM", None, 8),
+        71 => ("BC1_UNORM", Some("BC1"), 4),
+        72 => ("BC1_UNORM_SRGB", Some("BC1"), 4),
+        74 => ("BC2_UNORM", Some("BC2"), 8),
+        75 => ("BC2_UNORM_SRGB", Some("BC2"), 8),
+        77 => ("BC3_UNORM", Some("BC3"), 8),
+        78 => ("BC3_UNORM_SRGB", Some("BC3"), 8),
+        80 => ("BC4_UNORM", Some("BC4"), 4),
+        81 => ("BC4_SNORM", Some("BC4"), 4),
+        83 => ("BC5_UNORM", Some("BC5"), 8),
+        84 => ("BC5_SNORM", Some("BC5"), 8),
+        87 => ("B8G8R8A8_UNORM", None, 32),
+        88 => ("B8G8R8X8_UNORM", None, 32),
+        91 => ("B8G8R8A8_UNORM_SRGB", None, 32),
+        95 => ("BC6H_UF16", Some("BC6H"), 8),
+        96 => ("BC6H_SF16", Some("BC6H"), 8),
+        98 => ("BC7_UNORM", Some("BC7"), 8),
+        99 => ("BC7_UNORM_SRGB", Some("BC7"), 8),
+        _ => ("UNKNOWN", None, 0),
+    }
+}
+
+fn legacy_format(pf_flags: u32, four_cc: &[u8], bit_count: u32, r_mask: u32) -> (&'static str, Option<&'static str>, u32) {
+    if pf_flags & DDPF_FOURCC != 0 {
+        return match four_cc {
+            b"DXT1" => ("BC1_UNORM", Some("BC1"), 4),
+            b"DXT2" | b"DXT3" => ("BC2_UNORM", Some("BC2"), 8),
+            b"DXT4" | b"DXT5" => ("BC3_UNORM", Some("BC3"), 8),
+            b"ATI1" | b"BC4U" => ("BC4_UNORM", Some("BC4"), 4),
+            b"BC4S" => ("BC4_SNORM", Some("BC4"), 4),
+            b"ATI2" | b"BC5U" => ("BC5_UNORM", Some("BC5"), 8),
+            b"BC5S" => ("BC5_SNORM", Some("BC5"), 8),
+            _ => ("UNKNOWN", None, 0),
+        };
+    }
+    if pf_flags & DDPF_RGB != 0 {
+        return match (bit_count, r_mask) {
+            (32, 0x0000_00ff) => ("R8G8B8A8_UNORM", None, 32),
+            (32, _) if pf_flags & DDPF_ALPHAPIXELS != 0 => ("B8G8R8A8_UNORM", None, 32),
+            (32, _) => ("B8G8R8X8_UNORM", None, 32),
+            (24, _) => ("B8G8R8_UNORM", None, 24),
+            (16, _) => ("B5G6R5_UNORM", None, 16),
+            _ => ("UNKNOWN", None, bit_count),
+        };
+    }
+    if pf_flags & DDPF_LUMINANCE != 0 {
+        return if bit_count == 8 { ("R8_UNORM", None, 8) } else { ("R8G8_UNORM", None, 16) };
+    }
+    ("UNKNOWN", None, 0)
+}
+
+pub fn parse_dds_header(bytes: &[u8]) -> Result<DdsInfo, String> {
+    if bytes.len() < HEADER_LEN || &bytes[0..4] != DDS_MAGIC {
+        return Err("Not a DDS file".to_string());
+    }
+
+    let height = read_u32(bytes, 12);
+    let width = read_u32(bytes, 16);
+    let depth = read_u32(bytes, 24).max(1);
+    let mip_count = read_u32(bytes, 28).max(1);
+    let pf_flags = read_u32(bytes, 80);
+    let four_cc = &bytes[84..88];
+    let bit_count = read_u32(bytes, 88);
+    let r_mask = read_u32(bytes, 92);
+    let caps2 = read_u32(bytes, 112);
+
+    let mut array_size = 1;
+    let (format, compression, bits_per_pixel) = if pf_flags & DDPF_FOURCC != 0 && four_cc == b"DX10" {
+        if bytes.len() < HEADER_LEN + DX10_HEADER_LEN {
+            return Err("DDS file has a truncated DX10 header".to_string());
+        }
+        array_size = read_u32(bytes, HEADER_LEN + 12).max(1);
+        dxgi_format(read_u32(bytes, HEADER_LEN))
+    } else {
+        legacy_format(pf_flags, four_cc, bit_count, r_mask)
+    };
+
+    Ok(DdsInfo {
+        width,
+        height,
+        depth,
+        mip_count,
+        array_size,
+        is_cubemap: caps2 & DDSCAPS2_CUBEMAP != 0,
+        format: format.to_string(),
+        compression: compression.map(|c| c.to_string()),
+        bits_per_pixel,
+    })
+}
+
+pub fn read_dds_info(path: &Path) -> Result<DdsInfo, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut buf = Vec::with_capacity(HEADER_LEN + DX10_HEADER_LEN);
+    file.take((HEADER_LEN + DX10_HEADER_LEN) as u64)
+        .read_to_end(&mut buf)
+        .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    parse_dds_header(&buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(width: u32, height: u32, mip_count: u32, compression: Option<&str>, bits_per_pixel: u32) -> DdsInfo {
+        DdsInfo {
+            width,
+            height,
+            depth: 1,
+            mip_count,
+            array_size: 1,
+            is_cubemap: false,
+            format: String::new(),
+            compression: compression.map(|c| c.to_string()),
+            bits_per_pixel,
+        }
+    }
+
+    #[test]
+    fn sizes_block_compressed_mip_chains() {
+        // 8x8 BC7: 4 blocks, then 1 block for 4x4, 2x2 and 1x1
+        assert_eq!(info(8, 8, 4, Some("BC7"), 8).gpu_size_bytes(), Ok(16 * (4 + 1 + 1 + 1)));
+        assert_eq!(info(4, 4, 1, Some("BC1"), 4).gpu_size_bytes(), Ok(8));
+        assert_eq!(info(4, 4, 1, None, 32).gpu_size_bytes(), Ok(64));
+    }
+
+    #[test]
+    fn cubemaps_and_arrays_multiply() {
+        let mut cube = info(4, 4, 1, None, 32);
+        cube.is_cubemap = true;
+        cube.array_size = 2;
+        assert_eq!(cube.gpu_size_bytes(), Ok(64 * 6 * 2));
+    }
+
+    #[test]
+    fn overflowing_headers_are_errors() {
+        let mut huge = info(u32::MAX, u32::MAX, 1, None, 128);
+        huge.depth = u32::MAX;
+        assert!(huge.gpu_size_bytes().is_err());
+
+        let mut layers = info(u32::MAX, u32::MAX, 1, Some("BC7"), 8);
+        layers.array_size = u32::MAX;
+        layers.is_cubemap = true;
+        assert!(layers.gpu_size_bytes().is_err());
+    }
+}
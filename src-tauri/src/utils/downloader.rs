@@ -0,0 +1,411 @@
+use crate::configs::app_config::AppConfig;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Semaphore;
+
+// Streams a url into cache_dir/Downloads. Data goes to "<id>.part" first, so a failed
+// or cancelled download resumes with a Range request on the next attempt, and the
+// file only gets its real name once it is complete (and matches the hash, if given).
+// A resume is only attempted with the ETag / Last-Modified saved next to the .part
+// ("<id>.part.json"), sent as If-Range, so a changed file is never spliced onto old data.
+
+pub const MAX_CONCURRENT_DOWNLOADS: usize = 3;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+static DOWNLOAD_SLOTS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_DOWNLOADS);
+// One download per id at a time, they would share the .part file
+static ACTIVE_DOWNLOADS: Mutex<BTreeMap<String, Arc<tokio::sync::Mutex<()>>>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum DownloadState {
+    Queued,
+    Downloading,
+    Verifying,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+    pub id: String,
+    pub url: String,
+    pub state: DownloadState,
+    pub downloaded: u64,
+    pub total: Option<u64>, // None when the server sends no length
+    pub error: Option<String>,
+}
+
+// Stable id per url (across app and toolchain versions), also the name of the .part file
+pub fn download_id(url: &str) -> String {
+    format!("{:x}", Sha256::digest(url.as_bytes()))[..32].to_string()
+}
+
+// Identifies the remote file a .part belongs to
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct PartValidator {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl PartValidator {
+    fn from_response(resp: &reqwest::Response) -> Self {
+        let header = |name| resp.headers().get(name).and_then(|v| v.to_str().ok()).map(|v| v.to_string());
+        Self {
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+        }
+    }
+
+    // If-Range only accepts strong ETags
+    fn if_range(&self) -> Option<&str> {
+        self.etag
+            .as_deref()
+            .filter(|e| !e.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+}
+
+fn validator_path(part_path: &Path) -> PathBuf {
+    part_path.with_extension("part.json")
+}
+
+fn load_validator(part_path: &Path) -> Option<PartValidator> {
+    std::fs::read_to_string(validator_path(part_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+fn discard_part(part_path: &Path) {
+    let _ = std::fs::remove_file(part_path);
+    let _ = std::fs::remove_file(validator_path(part_path));
+}
+
+// "bytes 100-199/200" -> 100
+fn content_range_start(value: &str) -> Option<u64> {
+    value.trim().strip_prefix("bytes ")?.split('-').next()?.trim().parse().ok()
+}
+
+#[derive(Debug, PartialEq)]
+enum PartAction {
+    Append,    // 206 continuing exactly where the .part ends
+    Overwrite, // Full body, replaces the .part
+    Complete,  // 416 on a resume, the hash check decides
+    Discard,   // The .part cannot be trusted, download again from scratch
+}
+
+fn part_action(status: reqwest::StatusCode, content_range: Option<&str>, offset: u64, verify_hash: bool) -> Result<PartAction, String> {
+    use reqwest::StatusCode;
+    match status {
+        StatusCode::PARTIAL_CONTENT => match content_range.and_then(content_range_start) {
+            Some(start) if start == offset => Ok(PartAction::Append),
+            _ => Ok(PartAction::Discard),
+        },
+        // Only meaningful when resuming, without a hash there is no telling the .part is whole
+        StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => Ok(if verify_hash { PartAction::Complete } else { PartAction::Discard }),
+        // Range ignored, or If-Range did not match because the file changed
+        s if s.is_success() => Ok(PartAction::Overwrite),
+        s => Err(format!("Download failed: HTTP {}", s)),
+    }
+}
+
+pub fn get_download_dir() -> Result<PathBuf, String> {
+    let config = AppConfig::load().map_err(|e| e.to_string())?;
+    let base = if config.cache_dir.is_empty() {
+        std::env::temp_dir().join("SSMT4CachedFolder")
+    } else {
+        PathBuf::from(&config.cache_dir)
+    };
+    let dir = base.join("Downloads");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create download directory: {}", e))?;
+    Ok(dir)
+}
+
+fn file_name_from_response(resp: &reqwest::Response) -> Option<String> {
+    // Content-Disposition: attachment; filename="Mod.zip"
+    let disposition = resp.headers().get(reqwest::header::CONTENT_DISPOSITION)?.to_str().ok()?;
+    let name = disposition
+        .split(';')
+        .filter_map(|part| part.trim().strip_prefix("filename="))
+        .next()?
+        .trim_matches('"');
+    Some(name.to_string())
+}
+
+fn file_name_from_url(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next()?;
+    let name = path.rsplit('/').next()?;
+    name.contains('.').then(|| name.to_string())
+}
+
+// Drop anything that could point outside the download dir
+fn sanitize_file_name(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c.is_control() || "<>:\"|?*".contains(c) { '_' } else { c })
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('.').to_string();
+    if cleaned.is_empty() { "download".to_string() } else { cleaned }
+}
+
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = std::fs::File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn emit_progress(app: &AppHandle, progress: &DownloadProgress) {
+    let _ = app.emit("download-progress", progress);
+}
+
+// Downloads url into dir and returns the final path.
+// file_name overrides the name from Content-Disposition / the url.
+pub async fn download_file(
+    app: &AppHandle,
+    url: &str,
+    dir: &Path,
+    file_name: Option<&str>,
+    expected_sha256: Option<&str>,
+) -> Result<PathBuf, String> {
+    let id = download_id(url);
+    let mut progress = DownloadProgress {
+        id: id.clone(),
+        url: url.to_string(),
+        state: DownloadState::Queued,
+        downloaded: 0,
+        total: None,
+        error: None,
+    };
+    emit_progress(app, &progress);
+
+    let id_lock = ACTIVE_DOWNLOADS.lock().unwrap().entry(id.clone()).or_default().clone();
+    let result = {
+        let _guard = id_lock.lock().await;
+        download_inner(app, url, dir, file_name, expected_sha256, &mut progress).await
+    };
+    {
+        let mut active = ACTIVE_DOWNLOADS.lock().unwrap();
+        // Only the map and this call still hold it: nobody is waiting
+        if Arc::strong_count(&id_lock) == 2 {
+            active.remove(&id);
+        }
+    }
+    match &result {
+        Ok(path) => {
+            println!("[Download] Completed {} -> {:?}", url, path);
+            progress.state = DownloadState::Completed;
+        }
+        Err(e) => {
+            println!("[Download] Failed {}: {}", url, e);
+            progress.state = DownloadState::Failed;
+            progress.error = Some(e.clone());
+        }
+    }
+    emit_progress(app, &progress);
+    result
+}
+
+async fn download_inner(
+    app: &AppHandle,
+    url: &str,
+    dir: &Path,
+    file_name: Option<&str>,
+    expected_sha256: Option<&str>,
+    progress: &mut DownloadProgress,
+) -> Result<PathBuf, String> {
+    let _permit = DOWNLOAD_SLOTS.acquire().await.map_err(|e| e.to_string())?;
+
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create download directory: {}", e))?;
+    let part_path = dir.join(format!("{}.part", progress.id));
+    let verify_hash = expected_sha256.map(|h| !h.trim().is_empty()).unwrap_or(false);
+    let client = reqwest::Client::new();
+
+    // Second round only after a .part had to be discarded
+    let mut final_path = None;
+    for _ in 0..2 {
+        let validator = load_validator(&part_path);
+        let mut offset = std::fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+        let if_range = validator.as_ref().and_then(|v| v.if_range()).map(|v| v.to_string());
+        if offset > 0 && if_range.is_none() {
+            println!("[Download] No validator for the partial download of {}, starting over", url);
+            discard_part(&part_path);
+            offset = 0;
+        }
+
+        let mut request = client.get(url).header(reqwest::header::USER_AGENT, "ssmt4-app");
+        if let (true, Some(if_range)) = (offset > 0, &if_range) {
+            println!("[Download] Resuming {} from {} bytes", url, offset);
+            request = request
+                .header(reqwest::header::RANGE, format!("bytes={}-", offset))
+                .header(reqwest::header::IF_RANGE, if_range);
+        }
+        let resp = request.send().await.map_err(|e| format!("Download failed: {}", e))?;
+
+        let name = file_name
+            .map(|n| n.to_string())
+            .or_else(|| file_name_from_response(&resp))
+            .or_else(|| file_name_from_url(url))
+            .map(|n| sanitize_file_name(&n))
+            .unwrap_or_else(|| progress.id.clone());
+
+        let content_range = resp.headers().get(reqwest::header::CONTENT_RANGE).and_then(|v| v.to_str().ok()).map(|v| v.to_string());
+        let action = part_action(resp.status(), content_range.as_deref(), offset, verify_hash)?;
+        match action {
+            PartAction::Discard => {
+                println!("[Download] Partial download of {} does not match the server file, starting over", url);
+                discard_part(&part_path);
+                continue;
+            }
+            PartAction::Complete => {}
+            PartAction::Append | PartAction::Overwrite => {
+                let append = action == PartAction::Append;
+                if !append {
+                    offset = 0;
+                    let validator = PartValidator::from_response(&resp);
+                    if let Ok(content) = serde_json::to_string(&validator) {
+                        let _ = std::fs::write(validator_path(&part_path), content);
+                    }
+                }
+                write_part(app, resp, &part_path, offset, append, progress).await?;
+            }
+        }
+        final_path = Some(dir.join(&name));
+        break;
+    }
+    let final_path = final_path.ok_or_else(|| "Download failed: the server keeps sending a different file".to_string())?;
+
+    if let Some(expected) = expected_sha256.map(|h| h.trim().to_lowercase()).filter(|h| !h.is_empty()) {
+        progress.state = DownloadState::Verifying;
+        emit_progress(app, progress);
+        let path = part_path.clone();
+        let actual = tokio::task::spawn_blocking(move || sha256_file(&path))
+            .await
+            .map_err(|e| e.to_string())??;
+        if actual != expected {
+            // A corrupt .part would fail the same way on every resume
+            discard_part(&part_path);
+            return Err(format!("Checksum mismatch: expected {}, got {}", expected, actual));
+        }
+    }
+
+    if final_path.exists() {
+        std::fs::remove_file(&final_path).map_err(|e| format!("Failed to replace {:?}: {}", final_path, e))?;
+    }
+    std::fs::rename(&part_path, &final_path).map_err(|e| format!("Failed to finish download: {}", e))?;
+    let _ = std::fs::remove_file(validator_path(&part_path));
+    Ok(final_path)
+}
+
+async fn write_part(
+    app: &AppHandle,
+    resp: reqwest::Response,
+    part_path: &Path,
+    offset: u64,
+    append: bool,
+    progress: &mut DownloadProgress,
+) -> Result<(), String> {
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(part_path)
+        .await
+        .map_err(|e| format!("Failed to open {:?}: {}", part_path, e))?;
+
+    progress.state = DownloadState::Downloading;
+    progress.downloaded = offset;
+    progress.total = resp.content_length().map(|len| len + offset);
+    emit_progress(app, progress);
+
+    let mut last_emit = Instant::now();
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Download interrupted: {}", e))?;
+        file.write_all(&chunk).await.map_err(|e| format!("Failed to write file: {}", e))?;
+        progress.downloaded += chunk.len() as u64;
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            emit_progress(app, progress);
+            last_emit = Instant::now();
+        }
+    }
+    file.flush().await.map_err(|e| format!("Failed to write file: {}", e))?;
+
+    if let Some(total) = progress.total {
+        if progress.downloaded < total {
+            return Err(format!("Download incomplete: {} of {} bytes", progress.downloaded, total));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn download_id_is_stable() {
+        // sha256("https://example.com/a.zip"), must not change between builds
+        assert_eq!(download_id("https://example.com/a.zip"), "1d9defa430c34a6c6819693fcf47e725");
+        assert_eq!(download_id("https://example.com/a.zip").len(), 32);
+        assert_ne!(download_id("https://example.com/a.zip"), download_id("https://example.com/b.zip"));
+    }
+
+    #[test]
+    fn parses_content_range() {
+        assert_eq!(content_range_start("bytes 100-199/200"), Some(100));
+        assert_eq!(content_range_start("bytes 0-9/*"), Some(0));
+        assert_eq!(content_range_start("bytes */200"), None);
+        assert_eq!(content_range_start("items 1-2/3"), None);
+    }
+
+    #[test]
+    fn appends_only_when_the_range_continues_the_part() {
+        assert_eq!(part_action(StatusCode::PARTIAL_CONTENT, Some("bytes 100-199/200"), 100, false), Ok(PartAction::Append));
+        assert_eq!(part_action(StatusCode::PARTIAL_CONTENT, Some("bytes 50-199/200"), 100, false), Ok(PartAction::Discard));
+        assert_eq!(part_action(StatusCode::PARTIAL_CONTENT, None, 100, false), Ok(PartAction::Discard));
+    }
+
+    #[test]
+    fn full_responses_replace_the_part() {
+        // Server ignored Range, or If-Range did not match
+        assert_eq!(part_action(StatusCode::OK, None, 100, false), Ok(PartAction::Overwrite));
+        assert_eq!(part_action(StatusCode::OK, None, 0, false), Ok(PartAction::Overwrite));
+        assert!(part_action(StatusCode::NOT_FOUND, None, 0, false).is_err());
+    }
+
+    #[test]
+    fn range_not_satisfiable_needs_a_hash() {
+        assert_eq!(part_action(StatusCode::RANGE_NOT_SATISFIABLE, None, 100, true), Ok(PartAction::Complete));
+        assert_eq!(part_action(StatusCode::RANGE_NOT_SATISFIABLE, None, 100, false), Ok(PartAction::Discard));
+        assert!(part_action(StatusCode::RANGE_NOT_SATISFIABLE, None, 0, true).is_err());
+    }
+
+    #[test]
+    fn if_range_prefers_strong_etags() {
+        let strong = PartValidator { etag: Some("\"abc\"".into()), last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".into()) };
+        assert_eq!(strong.if_range(), Some("\"abc\""));
+        let weak = PartValidator { etag: Some("W/\"abc\"".into()), last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".into()) };
+        assert_eq!(weak.if_range(), Some("Wed, 21 Oct 2015 07:28:00 GMT"));
+        assert_eq!(PartValidator::default().if_range(), None);
+    }
+}
@@ -133,7 +133,7 @@ fn find_source_games_dir(app: &AppHandle) -> Option<PathBuf> {
 }
 
 // 递归拷贝目录，如果目标文件存在则跳过
-fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+pub fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
     if !dst.exists() {
         fs::create_dir_all(dst)?;
     }
@@ -156,6 +156,24 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
     Ok(())
 }
 
+// 递归统计目录大小（字节），不跟随子目录中的符号链接
+pub fn get_dir_size(path: &Path) -> u64 {
+    let entries = match fs::read_dir(path) {
+        Ok(e) => e,
+        Err(_) => return 0,
+    };
+
+    let mut total = 0;
+    for entry in entries.flatten() {
+        match entry.file_type() {
+            Ok(ty) if ty.is_dir() => total += get_dir_size(&entry.path()),
+            Ok(ty) if ty.is_file() => total += entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => {}
+        }
+    }
+    total
+}
+
 pub fn check_and_create_dir(path: &Path) -> std::io::Result<()> {
     if !path.exists() {
         fs::create_dir_all(path)?;
@@ -156,11 +156,7 @@ impl IniManager {
 
             if clean_line.starts_with('[') && clean_line.ends_with(']') {
                 let current_section = clean_line[1..clean_line.len() - 1].trim().to_lowercase();
-                if current_section == section_lower {
-                    in_target_section = true;
-                } else {
-                    in_target_section = false;
-                }
+                in_target_section = current_section == section_lower;
                 continue;
             }
 
@@ -0,0 +1,484 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Read-only parser for 3DMigoto mod ini files.
+// Unlike IniManager (which edits d3dx.ini in place), this keeps line numbers and
+// duplicate keys so the analysis commands can point back at the exact line.
+
+#[derive(Debug, Clone)]
+pub struct IniEntry {
+    pub key: String,   // Text before '=', or the flow keyword (if/elif/else/endif)
+    pub value: String, // Text after '=', or the rest of a flow line
+    pub line: usize,   // 1-based
+}
+
+#[derive(Debug, Clone)]
+pub struct IniSection {
+    pub name: String, // Without brackets, original case
+    pub line: usize,
+    pub entries: Vec<IniEntry>,
+}
+
+impl IniSection {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.key.eq_ignore_ascii_case(key))
+            .map(|e| e.value.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModIni {
+    pub path: PathBuf,
+    pub namespace: Option<String>, // Explicit `namespace =` declared before the first section
+    pub preamble: Vec<IniEntry>,   // Entries before the first section
+    pub sections: Vec<IniSection>,
+}
+
+impl ModIni {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let bytes = fs::read(&path).map_err(|e| format!("Failed to read ini file {:?}: {}", path, e))?;
+        let content = String::from_utf8_lossy(&bytes);
+        Ok(Self::parse(path, &content))
+    }
+
+    pub fn parse(path: PathBuf, content: &str) -> Self {
+        let mut preamble = Vec::new();
+        let mut sections: Vec<IniSection> = Vec::new();
+
+        for (i, raw) in content.lines().enumerate() {
+            let line = i + 1;
+            let trimmed = raw.trim().trim_start_matches('\u{feff}');
+            if trimmed.is_empty() || trimmed.starts_with(';') || trimmed.starts_with('#') {
+                continue;
+            }
+
+            if trimmed.starts_with('[') {
+                let name = trimmed
+                    .trim_start_matches('[')
+                    .split(']')
+                    .next()
+                    .unwrap_or("")
+                    .trim()
+                    .to_string();
+                sections.push(IniSection { name, line, entries: Vec::new() });
+                continue;
+            }
+
+            let entry = parse_entry(trimmed, line);
+            match sections.last_mut() {
+                Some(section) => section.entries.push(entry),
+                None => preamble.push(entry),
+            }
+        }
+
+        let namespace = preamble
+            .iter()
+            .find(|e| e.key.eq_ignore_ascii_case("namespace"))
+            .map(|e| e.value.clone())
+            .filter(|v| !v.is_empty());
+
+        Self { path, namespace, preamble, sections }
+    }
+
+    // Namespace 3DMigoto assigns to this file: the explicit one, or the ini path relative to
+    // the 3DMigoto folder (e.g. "mods\ayaka\ayaka.ini"). Lowercased with '\' separators.
+    pub fn effective_namespace(&self, install_dir: &Path) -> String {
+        match &self.namespace {
+            Some(ns) => normalize_namespace(ns),
+            None => {
+                let relative = self.path.strip_prefix(install_dir).unwrap_or(&self.path);
+                normalize_namespace(&relative.to_string_lossy())
+            }
+        }
+    }
+
+    pub fn sections_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a IniSection> + 'a {
+        self.sections.iter().filter(move |s| {
+            s.name
+                .get(..prefix.len())
+                .map(|head| head.eq_ignore_ascii_case(prefix))
+                .unwrap_or(false)
+        })
+    }
+}
+
+// A reference into another namespace, e.g. "run = CommandList\global\ORFix\ORFix"
+// or "if $\mods\lib\lib.ini\active == 1"
+#[derive(Debug, Clone)]
+pub struct NamespaceRef {
+    pub kind: String,      // "CommandList", "Resource", "CustomShader" or "$" for variables
+    pub namespace: String, // Normalized, see normalize_namespace
+    pub name: String,
+    pub line: usize,
+}
+
+const REFERENCE_PREFIXES: [&str; 3] = ["CommandList\\", "Resource\\", "CustomShader\\"];
+
+impl ModIni {
+    pub fn namespace_references(&self) -> Vec<NamespaceRef> {
+        let mut refs = Vec::new();
+        for section in &self.sections {
+            for entry in &section.entries {
+                for text in [&entry.key, &entry.value] {
+                    let tokens = text.split(|c: char| {
+                        c.is_whitespace() || matches!(c, ',' | '(' | ')' | '=' | '!' | '<' | '>' | '&' | '|' | '+' | '*')
+                    });
+                    for token in tokens {
+                        if let Some(r) = parse_reference(token, entry.line) {
+                            refs.push(r);
+                        }
+                    }
+                }
+            }
+        }
+        refs
+    }
+}
+
+fn parse_reference(token: &str, line: usize) -> Option<NamespaceRef> {
+    let (kind, rest) = if let Some(rest) = token.strip_prefix("$\\") {
+        ("$".to_string(), rest)
+    } else {
+        let prefix = REFERENCE_PREFIXES.iter().find(|p| {
+            token.get(..p.len()).map(|head| head.eq_ignore_ascii_case(p)).unwrap_or(false)
+        })?;
+        (prefix.trim_end_matches('\\').to_string(), &token[prefix.len()..])
+    };
+
+    let (namespace, name) = rest.rsplit_once('\\')?;
+    if namespace.is_empty() || name.is_empty() {
+        return None;
+    }
+    Some(NamespaceRef {
+        kind,
+        namespace: normalize_namespace(namespace),
+        name: name.to_string(),
+        line,
+    })
+}
+
+fn parse_entry(trimmed: &str, line: usize) -> IniEntry {
+    for keyword in ["else if", "elif", "if", "else", "endif"] {
+        let head = match trimmed.get(..keyword.len()) {
+            Some(h) => h,
+            None => continue,
+        };
+        let rest = &trimmed[keyword.len()..];
+        if head.eq_ignore_ascii_case(keyword)
+            && (rest.is_empty() || rest.starts_with(|c: char| c.is_whitespace() || c == '('))
+        {
+            return IniEntry {
+                key: keyword.to_string(),
+                value: rest.trim().to_string(),
+                line,
+            };
+        }
+    }
+
+    match trimmed.find('=') {
+        Some(idx) => IniEntry {
+            key: trimmed[..idx].trim().to_string(),
+            value: trimmed[idx + 1..].trim().to_string(),
+            line,
+        },
+        None => IniEntry { key: trimmed.to_string(), value: String::new(), line },
+    }
+}
+
+pub fn normalize_namespace(ns: &str) -> String {
+    ns.trim().replace('/', "\\").trim_matches('\\').to_lowercase()
+}
+
+// A variable declaration inside [Constants], e.g. "global persist $swapvar = 0"
+#[derive(Debug, Clone)]
+pub struct VariableDecl {
+    pub name: String, // Without '$', original case
+    pub persist: bool,
+    pub default_value: String,
+    pub line: usize,
+}
+
+pub fn parse_variable_decl(entry: &IniEntry) -> Option<VariableDecl> {
+    let mut persist = false;
+    let mut name = None;
+    for token in entry.key.split_whitespace() {
+        match token.to_lowercase().as_str() {
+            "persist" => persist = true,
+            "global" | "local" => {}
+            _ if token.starts_with('$') => name = Some(token[1..].to_string()),
+            _ => return None,
+        }
+    }
+    name.filter(|n| !n.is_empty()).map(|name| VariableDecl {
+        name,
+        persist,
+        default_value: entry.value.clone(),
+        line: entry.line,
+    })
+}
+
+pub fn is_disabled_name(name: &str) -> bool {
+    name.to_uppercase().starts_with("DISABLED")
+}
+
+#[derive(Debug, Clone)]
+pub struct ModIniFile {
+    pub mod_relative_path: String, // Folder under Mods that owns this ini (same as ModInfo.relative_path)
+    pub ini_path: PathBuf,
+    pub enabled: bool, // false if the file or any folder above it carries the DISABLED prefix
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum TruncateReason {
+    DepthLimit,  // Deeper than threeDMigoto.modScanDepth
+    SymlinkLoop, // Folder (or a link to it) was already scanned
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TruncatedScan {
+    pub relative_path: String,
+    pub reason: TruncateReason,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ModIniScan {
+    pub files: Vec<ModIniFile>, // Sorted by path
+    pub truncated: Vec<TruncatedScan>,
+}
+
+struct IniWalk<'a> {
+    mods_dir: &'a Path,
+    max_depth: usize,
+    visited: HashSet<PathBuf>, // Canonical paths of scanned folders
+    scan: ModIniScan,
+}
+
+// Every .ini under Mods, grouped by the mod folder that owns it.
+// A mod folder is the first folder on the way down that directly contains an ini,
+// matching how scan_mods decides what is a mod and what is a group.
+// max_depth is threeDMigoto.modScanDepth, the same limit scan_mods stops at.
+pub fn scan_mod_inis(mods_dir: &Path, max_depth: usize) -> ModIniScan {
+    let mut walk = IniWalk { mods_dir, max_depth, visited: HashSet::new(), scan: ModIniScan::default() };
+    collect_inis_in(&mut walk, mods_dir, None, false, 0);
+    walk.scan.files.sort_by(|a, b| a.ini_path.cmp(&b.ini_path));
+    walk.scan
+}
+
+// scan_mod_inis for callers without a place to report truncation, logs it instead
+pub fn collect_mod_inis(mods_dir: &Path, max_depth: usize) -> Vec<ModIniFile> {
+    let scan = scan_mod_inis(mods_dir, max_depth);
+    for t in &scan.truncated {
+        println!("[ModIni] Scan stopped at {} ({:?})", t.relative_path, t.reason);
+    }
+    scan.files
+}
+
+// Every .ini inside one mod folder (its variants), the folder itself is the mod root
+pub fn collect_variant_inis(mod_dir: &Path, max_depth: usize) -> Vec<ModIniFile> {
+    let mut walk = IniWalk { mods_dir: mod_dir, max_depth, visited: HashSet::new(), scan: ModIniScan::default() };
+    collect_inis_in(&mut walk, mod_dir, Some(""), false, 0);
+    walk.scan.files.sort_by(|a, b| a.ini_path.cmp(&b.ini_path));
+    walk.scan.files
+}
+
+fn collect_inis_in(walk: &mut IniWalk, dir: &Path, mod_root: Option<&str>, parent_disabled: bool, depth: usize) {
+    let relative_dir = || dir.strip_prefix(walk.mods_dir).unwrap_or(dir).to_string_lossy().to_string();
+    if depth > walk.max_depth {
+        let relative_path = relative_dir();
+        walk.scan.truncated.push(TruncatedScan { relative_path, reason: TruncateReason::DepthLimit });
+        return;
+    }
+    // Break symlink/junction loops: every real folder is read once
+    let canonical = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    if !walk.visited.insert(canonical) {
+        let relative_path = relative_dir();
+        walk.scan.truncated.push(TruncatedScan { relative_path, reason: TruncateReason::SymlinkLoop });
+        return;
+    }
+
+    let entries: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(r) => r.flatten().map(|e| e.path()).collect(),
+        Err(_) => return,
+    };
+
+    let is_ini = |p: &PathBuf| {
+        p.is_file()
+            && p.extension()
+                .map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("ini"))
+                .unwrap_or(false)
+    };
+
+    let own_root;
+    let mod_root = match mod_root {
+        Some(root) => Some(root),
+        // Loose inis directly in Mods do not make Mods a mod, scan_folder skips them too
+        None if depth > 0 && entries.iter().any(is_ini) => {
+            own_root = dir.strip_prefix(walk.mods_dir).unwrap_or(dir).to_string_lossy().to_string();
+            Some(own_root.as_str())
+        }
+        None => None,
+    };
+
+    for path in &entries {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        if path.is_dir() {
+            collect_inis_in(walk, path, mod_root, parent_disabled || is_disabled_name(&name), depth + 1);
+        } else if is_ini(path) {
+            if let Some(root) = mod_root {
+                walk.scan.files.push(ModIniFile {
+                    mod_relative_path: root.to_string(),
+                    ini_path: path.clone(),
+                    enabled: !parent_disabled && !is_disabled_name(&name),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::TempDir;
+
+    const SAMPLE: &str = "\u{feff}namespace = Mods\\Ayaka\n\
+        ; comment\n\
+        [Constants]\n\
+        global persist $swap = 0\n\
+        \n\
+        [TextureOverrideBody]\n\
+        hash = 1a2b3c4d\n\
+        hash = duplicate\n\
+        if $swap == 1\n\
+        \x20   run = CommandList\\global\\ORFix\\ORFix\n\
+        elif $\\mods\\lib\\lib.ini\\active == 1\n\
+        else\n\
+        endif\n\
+        ib = Resource\\Shared\\Lib\\BodyIB\n";
+
+    fn sample() -> ModIni {
+        ModIni::parse(PathBuf::from("Mods/Ayaka/ayaka.ini"), SAMPLE)
+    }
+
+    #[test]
+    fn parses_sections_entries_and_lines() {
+        let ini = sample();
+        assert_eq!(ini.namespace.as_deref(), Some("Mods\\Ayaka"));
+        assert_eq!(ini.sections.len(), 2);
+        assert_eq!(ini.sections[0].name, "Constants");
+        assert_eq!(ini.sections[0].line, 3);
+
+        let body = &ini.sections[1];
+        assert_eq!(body.name, "TextureOverrideBody");
+        // Duplicate keys are kept in order, get() returns the first
+        assert_eq!(body.entries.iter().filter(|e| e.key == "hash").count(), 2);
+        assert_eq!(body.get("HASH"), Some("1a2b3c4d"));
+        assert_eq!(body.entries[0].line, 7);
+    }
+
+    #[test]
+    fn parses_flow_keywords() {
+        let ini = sample();
+        let keys: Vec<&str> = ini.sections[1].entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["hash", "hash", "if", "run", "elif", "else", "endif", "ib"]);
+        assert_eq!(ini.sections[1].entries[2].value, "$swap == 1");
+        // "iffy = 1" is a key, not an if
+        assert_eq!(parse_entry("iffy = 1", 1).key, "iffy");
+    }
+
+    #[test]
+    fn finds_namespace_references() {
+        let refs = sample().namespace_references();
+        let found: Vec<(String, String, String)> = refs.into_iter().map(|r| (r.kind, r.namespace, r.name)).collect();
+        assert!(found.contains(&("CommandList".into(), "global\\orfix".into(), "ORFix".into())));
+        assert!(found.contains(&("$".into(), "mods\\lib\\lib.ini".into(), "active".into())));
+        assert!(found.contains(&("Resource".into(), "shared\\lib".into(), "BodyIB".into())));
+    }
+
+    #[test]
+    fn parses_variable_declarations() {
+        let ini = sample();
+        let decl = parse_variable_decl(&ini.sections[0].entries[0]).unwrap();
+        assert_eq!(decl.name, "swap");
+        assert!(decl.persist);
+        assert_eq!(decl.default_value, "0");
+        assert!(parse_variable_decl(&parse_entry("hash = 1", 1)).is_none());
+    }
+
+    #[test]
+    fn effective_namespace_falls_back_to_path() {
+        let ini = ModIni::parse(PathBuf::from("/game/Mods/Raiden/raiden.ini"), "[TextureOverrideA]\nhash = 1\n");
+        assert_eq!(ini.effective_namespace(Path::new("/game")), "mods\\raiden\\raiden.ini");
+        assert_eq!(sample().effective_namespace(Path::new("/game")), "mods\\ayaka");
+    }
+
+    #[test]
+    fn collects_inis_by_owning_mod() {
+        let dir = TempDir::new("mod_ini_collect");
+        dir.write("Char/Ayaka/ayaka.ini", "");
+        dir.write("Char/Ayaka/Variants/DISABLED_alt.ini", "");
+        dir.write("DISABLED_Off/off.ini", "");
+        dir.write("Char/readme.txt", "");
+
+        let files = collect_mod_inis(dir.path(), 5);
+        let summary: Vec<(String, String, bool)> = files
+            .iter()
+            .map(|f| (
+                f.mod_relative_path.replace('\\', "/"),
+                f.ini_path.file_name().unwrap().to_string_lossy().to_string(),
+                f.enabled,
+            ))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                // Sorted by path, Variants/ sorts before ayaka.ini
+                ("Char/Ayaka".to_string(), "DISABLED_alt.ini".to_string(), false),
+                ("Char/Ayaka".to_string(), "ayaka.ini".to_string(), true),
+                ("DISABLED_Off".to_string(), "off.ini".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn stray_ini_in_mods_is_not_a_mod() {
+        let dir = TempDir::new("mod_ini_stray");
+        dir.write("foo.ini", "");
+        dir.write("Char/Ayaka/ayaka.ini", "");
+
+        let files = collect_mod_inis(dir.path(), 5);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].mod_relative_path.replace('\\', "/"), "Char/Ayaka");
+    }
+
+    #[test]
+    fn variants_include_inis_at_the_mod_root() {
+        let dir = TempDir::new("mod_ini_variants");
+        dir.write("ayaka.ini", "");
+        dir.write("Variants/DISABLED_alt.ini", "");
+
+        let files = collect_variant_inis(dir.path(), 5);
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|f| f.mod_relative_path.is_empty()));
+    }
+
+    #[test]
+    fn reports_inis_below_the_depth_limit() {
+        let dir = TempDir::new("mod_ini_depth");
+        dir.write("A/B/C/deep.ini", "");
+        dir.write("A/top.ini", "");
+
+        let scan = scan_mod_inis(dir.path(), 2);
+        assert_eq!(scan.files.len(), 1);
+        assert_eq!(scan.truncated.len(), 1);
+        assert_eq!(scan.truncated[0].relative_path.replace('\\', "/"), "A/B/C");
+        assert_eq!(scan.truncated[0].reason, TruncateReason::DepthLimit);
+
+        assert_eq!(scan_mod_inis(dir.path(), 3).files.len(), 2);
+    }
+}
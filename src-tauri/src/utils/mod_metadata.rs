@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+// Per-mod settings owned by the launcher, stored next to the mod's own files so they
+// survive renames, group moves and copies between games.
+pub const METADATA_FILE: &str = "ssmt4_mod.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModMetadata {
+    // Download page the mod came from (GameBanana etc.), used for update checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    // Version / publish time (unix seconds) of the files currently installed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<u64>,
+}
+
+pub fn load_mod_metadata(mod_dir: &Path) -> ModMetadata {
+    fs::read_to_string(mod_dir.join(METADATA_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_mod_metadata(mod_dir: &Path, metadata: &ModMetadata) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(metadata)
+        .map_err(|e| format!("Failed to serialize mod metadata: {}", e))?;
+    fs::write(mod_dir.join(METADATA_FILE), content)
+        .map_err(|e| format!("Failed to write mod metadata: {}", e))
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+// Resolves paths coming from the frontend against the Mods folder (or another trusted root).
+// Rejects absolute paths, "..", and anything that ends up outside the root after
+// canonicalizing, so a bad relative path can never make delete/rename touch other files.
+// The last component is not followed, so a symlinked mod (mod library deployment)
+// resolves to the link itself rather than its target.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModPathError {
+    Empty,
+    Absolute(String),
+    Traversal(String),
+    InvalidName(String),
+    OutsideRoot(String),
+    RootMissing(String),
+}
+
+impl fmt::Display for ModPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModPathError::Empty => write!(f, "Path is empty"),
+            ModPathError::Absolute(p) => write!(f, "Absolute paths are not allowed: {}", p),
+            ModPathError::Traversal(p) => write!(f, "Path must not contain '..': {}", p),
+            ModPathError::InvalidName(n) => write!(f, "Invalid name: {}", n),
+            ModPathError::OutsideRoot(p) => write!(f, "Path is outside the Mods folder: {}", p),
+            ModPathError::RootMissing(p) => write!(f, "Mods folder not found: {}", p),
+        }
+    }
+}
+
+impl From<ModPathError> for String {
+    fn from(e: ModPathError) -> Self {
+        e.to_string()
+    }
+}
+
+// Lexically clean a relative path, accepting both / and \ as separators
+fn normalize_relative(relative: &str) -> Result<PathBuf, ModPathError> {
+    let unified = relative.replace('\\', "/");
+    let mut clean = PathBuf::new();
+    for component in Path::new(&unified).components() {
+        match component {
+            Component::Normal(part) => clean.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => return Err(ModPathError::Traversal(relative.to_string())),
+            Component::RootDir | Component::Prefix(_) => return Err(ModPathError::Absolute(relative.to_string())),
+        }
+    }
+    // "C:foo" has no prefix component on Linux but is still a drive path on Windows
+    if clean.as_os_str().is_empty() {
+        return Err(ModPathError::Empty);
+    }
+    if unified.contains(':') {
+        return Err(ModPathError::Absolute(relative.to_string()));
+    }
+    Ok(clean)
+}
+
+// `root/relative`, guaranteed to stay below `root`. The target itself does not need to exist.
+pub fn resolve_under(root: &Path, relative: &str) -> Result<PathBuf, ModPathError> {
+    let clean = normalize_relative(relative)?;
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|_| ModPathError::RootMissing(root.to_string_lossy().to_string()))?;
+
+    let target = root.join(&clean);
+
+    // Canonicalize the closest existing ancestor of the target, but never the target itself
+    let mut ancestor = target.parent();
+    while let Some(dir) = ancestor {
+        if dir.exists() {
+            let canonical = dir
+                .canonicalize()
+                .map_err(|_| ModPathError::OutsideRoot(relative.to_string()))?;
+            if !canonical.starts_with(&canonical_root) {
+                return Err(ModPathError::OutsideRoot(relative.to_string()));
+            }
+            break;
+        }
+        ancestor = dir.parent();
+    }
+    Ok(target)
+}
+
+// Entry point for every mod command: a path relative to <installDir>/Mods
+pub fn resolve_mod_path(mods_dir: &Path, relative: &str) -> Result<PathBuf, ModPathError> {
+    resolve_under(mods_dir, relative)
+}
+
+// A single folder/file name typed by the user (rename, new group name)
+pub fn check_file_name(name: &str) -> Result<&str, ModPathError> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err(ModPathError::Empty);
+    }
+    let forbidden = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+    if trimmed == "." || trimmed == ".." || trimmed.contains(&forbidden[..]) {
+        return Err(ModPathError::InvalidName(name.to_string()));
+    }
+    Ok(trimmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_support::TempDir;
+
+    #[test]
+    fn resolves_relative_paths_below_root() {
+        let dir = TempDir::new("mod_paths_ok");
+        dir.write("Char/Mod/mod.ini", "");
+        assert_eq!(resolve_under(dir.path(), "Char/Mod").unwrap(), dir.path().join("Char").join("Mod"));
+        assert_eq!(resolve_under(dir.path(), r"Char\Mod").unwrap(), dir.path().join("Char").join("Mod"));
+        assert_eq!(resolve_under(dir.path(), "./Char/New").unwrap(), dir.path().join("Char").join("New"));
+        // Targets that do not exist yet are fine
+        assert!(resolve_under(dir.path(), "Missing/Deep/Path").is_ok());
+    }
+
+    #[test]
+    fn rejects_traversal() {
+        let dir = TempDir::new("mod_paths_traversal");
+        assert!(matches!(resolve_under(dir.path(), ".."), Err(ModPathError::Traversal(_))));
+        assert!(matches!(resolve_under(dir.path(), "a/../../b"), Err(ModPathError::Traversal(_))));
+        assert!(matches!(resolve_under(dir.path(), r"a\..\b"), Err(ModPathError::Traversal(_))));
+    }
+
+    #[test]
+    fn rejects_absolute_and_drive_paths() {
+        let dir = TempDir::new("mod_paths_absolute");
+        assert!(matches!(resolve_under(dir.path(), "/etc/passwd"), Err(ModPathError::Absolute(_))));
+        assert!(matches!(resolve_under(dir.path(), r"\Windows"), Err(ModPathError::Absolute(_))));
+        assert!(matches!(resolve_under(dir.path(), r"C:\Windows"), Err(ModPathError::Absolute(_))));
+        assert!(matches!(resolve_under(dir.path(), "C:Windows"), Err(ModPathError::Absolute(_))));
+        assert!(matches!(resolve_under(dir.path(), ""), Err(ModPathError::Empty)));
+        assert!(matches!(resolve_under(dir.path(), "./"), Err(ModPathError::Empty)));
+    }
+
+    #[test]
+    fn rejects_missing_root() {
+        let dir = TempDir::new("mod_paths_root");
+        assert!(matches!(resolve_under(&dir.path().join("nope"), "a"), Err(ModPathError::RootMissing(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_paths_through_links_out_of_root() {
+        let root = TempDir::new("mod_paths_root_link");
+        let outside = TempDir::new("mod_paths_outside");
+        std::os::unix::fs::symlink(outside.path(), root.path().join("escape")).unwrap();
+        assert!(matches!(resolve_under(root.path(), "escape/file"), Err(ModPathError::OutsideRoot(_))));
+        // The link itself is not followed, deleting it only removes the link
+        assert_eq!(resolve_under(root.path(), "escape").unwrap(), root.path().join("escape"));
+    }
+
+    #[test]
+    fn checks_file_names() {
+        assert_eq!(check_file_name("  My Mod ").unwrap(), "My Mod");
+        assert!(check_file_name("..").is_err());
+        assert!(check_file_name("a/b").is_err());
+        assert!(check_file_name("a:b").is_err());
+        assert!(check_file_name("   ").is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::future::Future;
+
+// Client for a mod hosting site. GameBanana is the only implementation, the trait lets
+// the update checker run against a local mock server (base url is configurable).
+
+pub const GAMEBANANA_API_URL: &str = "https://gamebanana.com/apiv11";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteModFile {
+    pub id: String,
+    pub name: String,
+    pub size: u64,
+    pub download_url: String,
+    pub added_at: u64, // Unix seconds
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteModInfo {
+    pub id: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub updated_at: u64, // Latest of the page update and file upload times
+    pub page_url: String,
+    pub files: Vec<RemoteModFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteChangelog {
+    pub title: String,
+    pub version: Option<String>,
+    pub date: u64,
+    pub entries: Vec<String>, // "Addition: ...", "Bugfix: ..."
+}
+
+pub trait ModSourceClient {
+    // Site specific mod id from a stored source url, None if the url is not for this site
+    fn parse_source(&self, source_url: &str) -> Option<String>;
+    fn fetch_mod(&self, id: &str) -> impl Future<Output = Result<RemoteModInfo, String>> + Send;
+    fn fetch_changelog(&self, id: &str) -> impl Future<Output = Result<Vec<RemoteChangelog>, String>> + Send;
+}
+
+pub struct GameBananaClient {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl GameBananaClient {
+    pub fn new(base_url: &str) -> Self {
+        let base_url = if base_url.trim().is_empty() { GAMEBANANA_API_URL } else { base_url.trim() };
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn get_json(&self, path: &str) -> Result<Value, String> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self
+            .client
+            .get(&url)
+            .header(reqwest::header::USER_AGENT, "ssmt4-app")
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+        if !resp.status().is_success() {
+            return Err(format!("GameBanana API Error: {} ({})", resp.status(), url));
+        }
+        resp.json().await.map_err(|e| format!("Failed to parse response: {}", e))
+    }
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn u64_field(value: &Value, key: &str) -> u64 {
+    value.get(key).and_then(|v| v.as_u64()).unwrap_or(0)
+}
+
+impl ModSourceClient for GameBananaClient {
+    // "https://gamebanana.com/mods/123456", "gamebanana:123456" or just "123456"
+    fn parse_source(&self, source_url: &str) -> Option<String> {
+        let url = source_url.trim().trim_end_matches('/');
+        let id = if let Some(rest) = url.strip_prefix("gamebanana:") {
+            rest
+        } else if url.contains("gamebanana.com/mods/") {
+            url.rsplit("/mods/").next()?.split(['/', '?', '#']).next()?
+        } else {
+            url
+        };
+        (!id.is_empty() && id.chars().all(|c| c.is_ascii_digit())).then(|| id.to_string())
+    }
+
+    async fn fetch_mod(&self, id: &str) -> Result<RemoteModInfo, String> {
+        let page = self.get_json(&format!("/Mod/{}/ProfilePage", id)).await?;
+
+        let files: Vec<RemoteModFile> = page
+            .get("_aFiles")
+            .and_then(|v| v.as_array())
+            .map(|files| {
+                files
+                    .iter()
+                    .map(|f| RemoteModFile {
+                        id: f.get("_idRow").map(|v| v.to_string()).unwrap_or_default(),
+                        name: str_field(f, "_sFile").unwrap_or_default(),
+                        size: u64_field(f, "_nFilesize"),
+                        download_url: str_field(f, "_sDownloadUrl").unwrap_or_default(),
+                        added_at: u64_field(f, "_tsDateAdded"),
+                        version: str_field(f, "_sVersion"),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let page_updated = u64_field(&page, "_tsDateUpdated").max(u64_field(&page, "_tsDateAdded"));
+        let newest_file = files.iter().map(|f| f.added_at).max().unwrap_or(0);
+
+        Ok(RemoteModInfo {
+            id: id.to_string(),
+            name: str_field(&page, "_sName").unwrap_or_default(),
+            version: str_field(&page, "_sVersion"),
+            updated_at: page_updated.max(newest_file),
+            page_url: str_field(&page, "_sProfileUrl").unwrap_or_else(|| format!("https://gamebanana.com/mods/{}", id)),
+            files,
+        })
+    }
+
+    async fn fetch_changelog(&self, id: &str) -> Result<Vec<RemoteChangelog>, String> {
+        let updates = self.get_json(&format!("/Mod/{}/Updates?_nPage=1&_nPerpage=10", id)).await?;
+
+        let records = updates.get("_aRecords").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        Ok(records
+            .iter()
+            .map(|r| RemoteChangelog {
+                title: str_field(r, "_sName").unwrap_or_default(),
+                version: str_field(r, "_sVersion"),
+                date: u64_field(r, "_tsDateAdded"),
+                entries: r
+                    .get("_aChangeLog")
+                    .and_then(|v| v.as_array())
+                    .map(|log| {
+                        log.iter()
+                            .filter_map(|e| {
+                                let text = str_field(e, "text")?;
+                                Some(match str_field(e, "cat") {
+                                    Some(cat) => format!("{}: {}", cat, text),
+                                    None => text,
+                                })
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    // Minimal HTTP server answering each request with the body for the first matching path prefix
+    async fn mock_server(routes: Vec<(&'static str, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = match listener.accept().await {
+                    Ok(s) => s,
+                    Err(_) => return,
+                };
+                let routes = routes.clone();
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                    let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+                    let response = match routes.iter().find(|(prefix, _)| path.starts_with(prefix)) {
+                        Some((_, body)) => format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            body.len(),
+                            body
+                        ),
+                        None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                    };
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[test]
+    fn parses_gamebanana_sources() {
+        let client = GameBananaClient::new("");
+        assert_eq!(client.parse_source("https://gamebanana.com/mods/123456").as_deref(), Some("123456"));
+        assert_eq!(client.parse_source("https://gamebanana.com/mods/123456/").as_deref(), Some("123456"));
+        assert_eq!(client.parse_source("https://gamebanana.com/mods/42?tab=files#x").as_deref(), Some("42"));
+        assert_eq!(client.parse_source("gamebanana:77").as_deref(), Some("77"));
+        assert_eq!(client.parse_source(" 99 ").as_deref(), Some("99"));
+        assert_eq!(client.parse_source("https://example.com/mods/1"), None);
+        assert_eq!(client.parse_source("gamebanana:abc"), None);
+        assert_eq!(client.parse_source(""), None);
+    }
+
+    #[tokio::test]
+    async fn fetches_mod_and_changelog_from_server() {
+        let base = mock_server(vec![
+            (
+                "/Mod/5/ProfilePage",
+                r#"{"_sName":"Cool Mod","_sVersion":"2.0","_tsDateUpdated":300,"_tsDateAdded":100,
+                    "_sProfileUrl":"https://gamebanana.com/mods/5",
+                    "_aFiles":[{"_idRow":11,"_sFile":"a.zip","_nFilesize":10,"_sDownloadUrl":"https://dl/11","_tsDateAdded":200},
+                               {"_idRow":12,"_sFile":"b.zip","_nFilesize":20,"_sDownloadUrl":"https://dl/12","_tsDateAdded":400,"_sVersion":"2.0"}]}"#,
+            ),
+            (
+                "/Mod/5/Updates",
+                r#"{"_aRecords":[{"_sName":"Big update","_sVersion":"2.0","_tsDateAdded":400,
+                    "_aChangeLog":[{"cat":"Addition","text":"New outfit"},{"text":"Misc"},{"cat":"Bugfix"}]}]}"#,
+            ),
+        ])
+        .await;
+        let client = GameBananaClient::new(&base);
+
+        let info = client.fetch_mod("5").await.unwrap();
+        assert_eq!(info.name, "Cool Mod");
+        assert_eq!(info.version.as_deref(), Some("2.0"));
+        assert_eq!(info.updated_at, 400);
+        assert_eq!(info.files.len(), 2);
+        assert_eq!(info.files[0].id, "11");
+        assert_eq!(info.files[1].added_at, 400);
+        assert_eq!(info.files[1].version.as_deref(), Some("2.0"));
+
+        let log = client.fetch_changelog("5").await.unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].entries, vec!["Addition: New outfit".to_string(), "Misc".to_string()]);
+
+        assert!(client.fetch_mod("6").await.unwrap_err().contains("404"));
+    }
+}
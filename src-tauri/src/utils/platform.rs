@@ -0,0 +1,381 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+
+// OS specific process spawning, folder reveal and recycle bin handling.
+// Everything else in the backend should go through here instead of calling
+// powershell / explorer directly, so the crate builds and runs on Linux too.
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+// Command that does not pop up a console window on Windows
+pub fn hidden_command(program: impl AsRef<std::ffi::OsStr>) -> Command {
+    #[allow(unused_mut)]
+    let mut cmd = Command::new(program);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    cmd
+}
+
+// Quote one argument for a Windows command line (CommandLineToArgvW rules)
+#[cfg(windows)]
+pub fn quote_windows_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_string();
+    }
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                quoted.push('"');
+                backslashes = 0;
+            }
+            _ => {
+                quoted.push_str(&"\\".repeat(backslashes));
+                quoted.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+// What to start: the executable, its arguments (one entry per argument, unquoted)
+// and the working directory. Quoting for the shell / elevation layer happens here.
+#[derive(Debug, Clone)]
+pub struct LaunchSpec {
+    pub exe: PathBuf,
+    pub args: Vec<String>,
+    pub work_dir: PathBuf,
+}
+
+impl LaunchSpec {
+    pub fn new(exe: &Path, work_dir: &Path) -> Self {
+        Self { exe: exe.to_path_buf(), args: Vec::new(), work_dir: work_dir.to_path_buf() }
+    }
+
+    pub fn args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+        self
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if !self.work_dir.is_dir() {
+            return Err(format!("Working directory not found: {:?}", self.work_dir));
+        }
+        Ok(())
+    }
+}
+
+// Split a user-entered argument string (-popupwindow -screen-width 1920 "C:\My Dir")
+// into arguments. Double quotes group, \" is a literal quote, other backslashes stay as-is.
+pub fn split_launch_args(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+                in_arg = true;
+            }
+            '"' => {
+                quoted = !quoted;
+                in_arg = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+// Start a program detached from the launcher.
+// Windows goes through Start-Process so UAC prompts get focus; elsewhere it is a plain spawn.
+// Returns the child when it is the program itself (not a powershell wrapper).
+pub fn start_process(spec: &LaunchSpec) -> Result<Option<Child>, String> {
+    spec.check()?;
+
+    #[cfg(windows)]
+    {
+        // Paths and arguments are handed over in the environment, the script text is constant.
+        // Start-Process passes a single ArgumentList string through verbatim, so it is quoted here.
+        const SCRIPT: &str = "$ErrorActionPreference = 'Stop'; \
+            $p = @{ FilePath = $env:SSMT4_LAUNCH_EXE; WorkingDirectory = $env:SSMT4_LAUNCH_DIR; PassThru = $true }; \
+            if ($env:SSMT4_LAUNCH_ARGS) { $p.ArgumentList = $env:SSMT4_LAUNCH_ARGS }; \
+            try { $proc = Start-Process @p; [Console]::Out.Write($proc.Id) } \
+            catch { [Console]::Error.Write($_.Exception.Message); exit 1 }";
+        let arguments: Vec<String> = spec.args.iter().map(|a| quote_windows_arg(a)).collect();
+        let output = hidden_command("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+            .env("SSMT4_LAUNCH_EXE", &spec.exe)
+            .env("SSMT4_LAUNCH_DIR", &spec.work_dir)
+            .env("SSMT4_LAUNCH_ARGS", arguments.join(" "))
+            .output()
+            .map_err(|e| format!("Failed to run powershell: {}", e))?;
+        // Start-Process returns once the process exists (or UAC was declined), so failures are real
+        if !output.status.success() {
+            let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(format!(
+                "Failed to launch {:?}: {}",
+                spec.exe,
+                if message.is_empty() { format!("powershell exited with {:?}", output.status.code()) } else { message }
+            ));
+        }
+        println!("[Platform] Started {:?} (pid {})", spec.exe, String::from_utf8_lossy(&output.stdout).trim());
+        Ok(None)
+    }
+
+    #[cfg(not(windows))]
+    {
+        let child = Command::new(&spec.exe)
+            .args(&spec.args)
+            .current_dir(&spec.work_dir)
+            .spawn()
+            .map_err(|e| format!("Failed to launch {:?}: {}", spec.exe, e))?;
+        Ok(Some(child))
+    }
+}
+
+// How a game config's Windows executables are started ("runner" in threeDMigoto config).
+// Run.exe and the game must share a runner so the loader sees the game in the same prefix.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LaunchRunner {
+    #[default]
+    Native,
+    #[serde(rename_all = "camelCase")]
+    Wine {
+        wine_path: Option<String>, // Empty = "wine" from PATH
+        prefix_path: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Proton {
+        proton_path: String, // Proton install folder or its "proton" script
+        compat_data_path: String, // steamapps/compatdata/<appid>
+        steam_path: Option<String>, // Empty = ~/.steam/steam
+    },
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn runner_command(runner: &LaunchRunner, exe: &Path) -> Result<Command, String> {
+    let mut cmd = match runner {
+        LaunchRunner::Native => return Ok(Command::new(exe)),
+        LaunchRunner::Wine { wine_path, prefix_path } => {
+            let prefix = PathBuf::from(prefix_path.trim());
+            if prefix_path.trim().is_empty() || !prefix.is_dir() {
+                return Err(format!("Wine prefix not found: {}", prefix_path));
+            }
+            let mut cmd = Command::new(non_empty(wine_path).unwrap_or("wine"));
+            cmd.env("WINEPREFIX", &prefix).arg(exe);
+            cmd
+        }
+        LaunchRunner::Proton { proton_path, compat_data_path, steam_path } => {
+            let mut proton = PathBuf::from(proton_path.trim());
+            if proton.is_dir() {
+                proton = proton.join("proton");
+            }
+            if !proton.is_file() {
+                return Err(format!("Proton not found: {}", proton_path));
+            }
+            if compat_data_path.trim().is_empty() {
+                return Err("Proton compatdata path is not set".to_string());
+            }
+            // Proton creates the prefix inside compatdata on first run
+            std::fs::create_dir_all(compat_data_path.trim())
+                .map_err(|e| format!("Failed to create compatdata folder: {}", e))?;
+            let steam = match non_empty(steam_path) {
+                Some(p) => PathBuf::from(p),
+                None => std::env::var("HOME").map(|h| PathBuf::from(h).join(".steam").join("steam")).unwrap_or_default(),
+            };
+            let mut cmd = Command::new(proton);
+            cmd.env("STEAM_COMPAT_DATA_PATH", compat_data_path.trim())
+                .env("STEAM_COMPAT_CLIENT_INSTALL_PATH", steam)
+                .arg("run")
+                .arg(exe);
+            cmd
+        }
+    };
+    // Prefer the 3DMigoto d3d11.dll next to the game over Wine's builtin / DXVK, unless the user set overrides
+    if std::env::var_os("WINEDLLOVERRIDES").is_none() {
+        cmd.env("WINEDLLOVERRIDES", "d3d11=n,b");
+    }
+    Ok(cmd)
+}
+
+// Path as a program inside the runner sees it. Wine and Proton map the host root to Z:,
+// so "/home/me/game.exe" becomes "Z:\home\me\game.exe". Windows style paths are kept.
+pub fn to_runner_path(runner: &LaunchRunner, path: &str) -> String {
+    if *runner == LaunchRunner::Native || !path.starts_with('/') {
+        return path.to_string();
+    }
+    format!("Z:{}", path.replace('/', "\\"))
+}
+
+// start_process through a runner. Wine / Proton spawn directly, there is no UAC to deal with.
+pub fn start_process_with(runner: &LaunchRunner, spec: &LaunchSpec) -> Result<Option<Child>, String> {
+    if *runner == LaunchRunner::Native {
+        return start_process(spec);
+    }
+    if cfg!(windows) {
+        return Err("Wine / Proton runners are only available on Linux".to_string());
+    }
+    spec.check()?;
+    println!("[Platform] Launching {:?} via {:?}", spec.exe, runner);
+    let child = runner_command(runner, &spec.exe)?
+        .args(&spec.args)
+        .current_dir(&spec.work_dir)
+        .spawn()
+        .map_err(|e| format!("Failed to launch {:?}: {}", spec.exe, e))?;
+    Ok(Some(child))
+}
+
+// Open a folder (or the folder containing a file) in the system file manager
+pub fn reveal_in_file_manager(path: &Path) -> Result<(), String> {
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut cmd = Command::new("explorer");
+        cmd.arg(path);
+        cmd
+    };
+    #[cfg(target_os = "macos")]
+    let mut cmd = {
+        let mut cmd = Command::new("open");
+        cmd.arg(path);
+        cmd
+    };
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let mut cmd = {
+        // xdg-open on a file would open it in its editor, show the parent instead
+        let target = if path.is_file() { path.parent().unwrap_or(path) } else { path };
+        let mut cmd = Command::new("xdg-open");
+        cmd.arg(target);
+        cmd
+    };
+
+    cmd.spawn().map_err(|e| format!("Failed to open file manager: {}", e))?;
+    Ok(())
+}
+
+// Move a file or folder to the recycle bin / trash. Errors when there is no trash, the
+// caller decides whether to delete_permanently instead.
+pub fn move_to_trash(path: &Path) -> Result<(), String> {
+    #[cfg(windows)]
+    {
+        // The path goes in through the environment, the script text is fixed
+        let script = "Add-Type -AssemblyName Microsoft.VisualBasic; \
+                      $fs = [Microsoft.VisualBasic.FileIO.FileSystem]; \
+                      if ($env:SSMT4_TRASH_IS_DIR -eq '1') { $fs::DeleteDirectory($env:SSMT4_TRASH_PATH, 'OnlyErrorDialogs', 'SendToRecycleBin') } \
+                      else { $fs::DeleteFile($env:SSMT4_TRASH_PATH, 'OnlyErrorDialogs', 'SendToRecycleBin') }";
+        let status = hidden_command("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", script])
+            .env("SSMT4_TRASH_PATH", path)
+            .env("SSMT4_TRASH_IS_DIR", if path.is_dir() { "1" } else { "0" })
+            .status()
+            .map_err(|e| format!("Failed to run recycle bin command: {}", e))?;
+        if !status.success() {
+            return Err("Failed to move to recycle bin".to_string());
+        }
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    {
+        // gio knows the freedesktop trash layout, including trash dirs on other mounts
+        let output = Command::new("gio")
+            .arg("trash")
+            .arg(path)
+            .output()
+            .map_err(|e| format!("Trash is not available (gio: {})", e))?;
+        if !output.status.success() {
+            return Err(format!("Failed to move to trash: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(())
+    }
+}
+
+// Delete without a trash, for when the user confirmed it after move_to_trash failed
+pub fn delete_permanently(path: &Path) -> Result<(), String> {
+    // symlink_metadata: a linked folder (library deployment) only loses the link
+    let is_real_dir = std::fs::symlink_metadata(path).map(|m| m.is_dir()).unwrap_or(false);
+    let result = if is_real_dir { std::fs::remove_dir_all(path) } else { std::fs::remove_file(path) };
+    result.map_err(|e| format!("Failed to delete {:?}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(line: &str) -> Vec<String> {
+        split_launch_args(line)
+    }
+
+    #[test]
+    fn splits_on_whitespace_runs() {
+        assert_eq!(split("-a  -b\t-c \n d"), vec!["-a", "-b", "-c", "d"]);
+        assert!(split("   ").is_empty());
+        assert!(split("").is_empty());
+    }
+
+    #[test]
+    fn double_quotes_group_arguments() {
+        assert_eq!(split(r#"-path "C:\My Games\x.exe" -w 1920"#), vec!["-path", r"C:\My Games\x.exe", "-w", "1920"]);
+        assert_eq!(split(r#"--name="a b""#), vec!["--name=a b"]);
+        // Empty quotes are still an argument
+        assert_eq!(split(r#"a "" b"#), vec!["a", "", "b"]);
+    }
+
+    #[test]
+    fn escaped_quotes_are_literal() {
+        assert_eq!(split(r#"say \"hi\""#), vec!["say", "\"hi\""]);
+        assert_eq!(split(r#""a \" b""#), vec!["a \" b"]);
+        // Other backslashes are kept, paths stay intact
+        assert_eq!(split(r"C:\Games\x.exe"), vec![r"C:\Games\x.exe"]);
+    }
+
+    #[test]
+    fn maps_host_paths_into_wine() {
+        let wine = LaunchRunner::Wine { wine_path: None, prefix_path: "/pfx".to_string() };
+        assert_eq!(to_runner_path(&wine, "/home/me/Games/GI/GenshinImpact.exe"), r"Z:\home\me\Games\GI\GenshinImpact.exe");
+        assert_eq!(to_runner_path(&wine, r"C:\Games\x.exe"), r"C:\Games\x.exe");
+        assert_eq!(to_runner_path(&LaunchRunner::Native, "/home/me/x.exe"), "/home/me/x.exe");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn quotes_windows_arguments() {
+        assert_eq!(quote_windows_arg("plain"), "plain");
+        assert_eq!(quote_windows_arg(""), "\"\"");
+        assert_eq!(quote_windows_arg("a b"), "\"a b\"");
+        assert_eq!(quote_windows_arg("say \"hi\""), r#""say \"hi\"""#);
+        // Trailing backslashes are doubled so they do not escape the closing quote
+        assert_eq!(quote_windows_arg(r"C:\My Dir\"), r#""C:\My Dir\\""#);
+        assert_eq!(quote_windows_arg(r"C:\NoSpace\"), r"C:\NoSpace\");
+    }
+}